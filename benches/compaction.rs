@@ -0,0 +1,42 @@
+//! Times membership queries against a set before and after `compact`.
+//!
+//! Run with `cargo bench --bench compaction`. Each `add` and `remove` leaves
+//! another condition for `has` to check, so the time per query grows with
+//! the number of operations until `compact` collapses them into one lookup.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use algae_rs::algaeset::AlgaeSet;
+
+const QUERIES: i64 = 100_000;
+
+fn nanos_per_query(set: &AlgaeSet<i64>, sample: &[i64]) -> f64 {
+    let start = Instant::now();
+    let mut found = 0;
+    for i in 0..QUERIES {
+        if set.has(&black_box(sample[i as usize % sample.len()])) {
+            found += 1;
+        }
+    }
+    black_box(found);
+    start.elapsed().as_nanos() as f64 / QUERIES as f64
+}
+
+fn main() {
+    for operations in [10, 100, 1_000] {
+        let mut set = AlgaeSet::<i64>::of(|x| x % 2 == 0);
+        for x in 0..operations {
+            set.add(&(2 * x + 1));
+            set.remove(&(4 * x));
+        }
+        let sample: Vec<i64> = (-100..4 * operations + 100).collect();
+        let uncompacted = nanos_per_query(&set, &sample);
+        set.compact(&sample);
+        let compacted = nanos_per_query(&set, &sample);
+        println!(
+            "{operations:>5} operations: {uncompacted:>8.1} ns/query, \
+             {compacted:>6.1} ns/query after compact"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Times membership queries against explicit sets of growing size.
+//!
+//! Run with `cargo bench --bench explicit_membership`. Sets built by
+//! `from_elements` keep their members in one hashed lookup, so the time per
+//! query should stay flat as the sets grow, including after `add`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use algae_rs::algaeset::AlgaeSet;
+
+const QUERIES: u64 = 1_000_000;
+
+fn nanos_per_query(set: &AlgaeSet<u64>, size: u64) -> f64 {
+    let start = Instant::now();
+    let mut found = 0;
+    for i in 0..QUERIES {
+        // Alternate between members and non-members
+        if set.has(&black_box(i * 7919 % (2 * size))) {
+            found += 1;
+        }
+    }
+    black_box(found);
+    start.elapsed().as_nanos() as f64 / QUERIES as f64
+}
+
+fn main() {
+    for size in [1_000, 10_000, 100_000, 1_000_000] {
+        let elements: Vec<u64> = (0..size).collect();
+        let mut set = AlgaeSet::from_elements(&elements);
+        let built = nanos_per_query(&set, size);
+        for x in size..size + 1_000 {
+            set.add(&x);
+        }
+        let extended = nanos_per_query(&set, size);
+        println!("{size:>9} elements: {built:>6.1} ns/query, {extended:>6.1} ns/query after add");
+    }
+}
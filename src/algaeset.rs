@@ -1,3 +1,49 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// A record of the set operations used to build an [`AlgaeSet`].
+///
+/// Conditions are opaque closures, so an [`AlgaeSet`] keeps this expression
+/// tree alongside them to describe how it was built. Sets are named by
+/// [`named`](fn@AlgaeSet::named); unnamed sets are rendered as `set`.
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::algaeset::AlgaeSet;
+///
+/// let mut odds = AlgaeSet::<i32>::of(|x| x % 2 != 0).named("odds");
+/// odds.add(&4);
+/// odds.remove(&1);
+/// assert!(odds.provenance().to_string() == "((odds ∪ {4}) \\ {1})");
+/// ```
+#[derive(Clone)]
+pub enum Provenance<E> {
+    Atom(String),
+    Union(Box<Provenance<E>>, Box<Provenance<E>>),
+    Intersection(Box<Provenance<E>>, Box<Provenance<E>>),
+    Difference(Box<Provenance<E>>, Box<Provenance<E>>),
+    Insertion(Box<Provenance<E>>, E),
+    Removal(Box<Provenance<E>>, E),
+    Complement(Box<Provenance<E>>),
+}
+
+impl<E: fmt::Debug> fmt::Display for Provenance<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Provenance::Atom(name) => write!(f, "{name}"),
+            Provenance::Union(left, right) => write!(f, "({left} ∪ {right})"),
+            Provenance::Intersection(left, right) => write!(f, "({left} ∩ {right})"),
+            Provenance::Difference(left, right) => write!(f, "({left} \\ {right})"),
+            Provenance::Insertion(set, element) => write!(f, "({set} ∪ {{{element:?}}})"),
+            Provenance::Removal(set, element) => write!(f, "({set} \\ {{{element:?}}})"),
+            Provenance::Complement(set) => write!(f, "{set}ᶜ"),
+        }
+    }
+}
+
 /// A representation of a ZF set.
 ///
 /// All elements must belong to a "supertype" `E`. Subsets of the supertype are
@@ -6,8 +52,22 @@
 ///
 /// Element existence (ie. whether or not a certain element is a member of a
 /// given set) is given through the [`has`](fn@AlgaeSet::has) function. Set
-/// unions are given by the [`or`](fn@AlgaeSet::or) function, and set
-/// intersections are given by the [`and`](fn@AlgaeSet::and) function.
+/// unions are given by the [`or`](fn@AlgaeSet::or) function, set
+/// intersections are given by the [`and`](fn@AlgaeSet::and) function, set
+/// differences are given by the [`minus`](fn@AlgaeSet::minus) function, and
+/// set complements are given by the [`complement`](fn@AlgaeSet::complement)
+/// function.
+///
+/// Set operations take effect in the order they're applied: a later
+/// [`add`](fn@AlgaeSet::add) or [`or`](fn@AlgaeSet::or) includes its elements
+/// even if they were removed earlier, and a later
+/// [`remove`](fn@AlgaeSet::remove), [`and`](fn@AlgaeSet::and), or
+/// [`minus`](fn@AlgaeSet::minus) excludes its elements however they were
+/// added. Each set also records these operations as a [`Provenance`]
+/// expression, given by the [`provenance`](fn@AlgaeSet::provenance) function.
+///
+/// Conditions take elements by reference, so `E` needn't be `Copy`; sets of
+/// `String`s or `Vec`s work just like sets of integers.
 ///
 /// # Examples
 ///
@@ -15,80 +75,806 @@
 /// use algae_rs::algaeset::AlgaeSet;
 ///
 /// let mut pos_floats = AlgaeSet::new(vec![
-///     Box::new(|e: f32| e > 0_f32)
+///     Box::new(|e: &f32| *e > 0_f32)
 /// ]);
 ///
-/// assert!(pos_floats.has(12_f32));
+/// assert!(pos_floats.has(&12_f32));
 ///
 /// let neg_floats = AlgaeSet::new(vec![
-///     Box::new(|e: f32| e < 0_f32)
+///     Box::new(|e: &f32| *e < 0_f32)
 /// ]);
 ///
 /// pos_floats.or(neg_floats);
 /// let all_floats = pos_floats;
-/// assert!(all_floats.has(12_f32));
-/// assert!(all_floats.has(-12_f32));
+/// assert!(all_floats.has(&12_f32));
+/// assert!(all_floats.has(&-12_f32));
+///
+/// let words = AlgaeSet::<String>::of(|w| w.chars().all(|c| "ab".contains(c)));
+/// assert!(words.has(&String::from("abba")));
+/// assert!(!words.has(&String::from("abc")));
 /// ```
 pub struct AlgaeSet<E> {
-    pos_conditions: Vec<Box<dyn Fn(E) -> bool>>,
-    neg_conditions: Vec<Box<dyn Fn(E) -> bool>>,
+    pos_conditions: Vec<Condition<E>>,
+    // Each negative condition is paired with the number of positive
+    // conditions preceding it, since only those added later override it
+    neg_conditions: Vec<(Condition<E>, usize)>,
+    // The hashed elements looked up by the positive condition at the given
+    // index, letting `add` extend explicit sets without a new condition
+    explicit: Option<(usize, Rc<dyn Explicit<E>>)>,
+    complemented: bool,
+    provenance: Provenance<E>,
+}
+
+type Condition<E> = Rc<dyn Fn(&E) -> bool>;
+type BoxedCondition<E> = Box<dyn Fn(&E) -> bool>;
+
+/// Hashed elements behind an explicit set, erasing the `Eq + Hash` bounds
+trait Explicit<E> {
+    fn contains(&self, element: &E) -> bool;
+
+    fn insert(&mut self, element: E);
+
+    /// Returns an unshared copy of the elements
+    fn copied(&self) -> Rc<dyn Explicit<E>>;
+}
+
+impl<E: Eq + Hash + Clone + 'static> Explicit<E> for HashSet<E> {
+    fn contains(&self, element: &E) -> bool {
+        HashSet::contains(self, element)
+    }
+
+    fn insert(&mut self, element: E) {
+        HashSet::insert(self, element);
+    }
+
+    fn copied(&self) -> Rc<dyn Explicit<E>> {
+        Rc::new(self.clone())
+    }
+}
+
+/// Returns the condition looking `element`s up in `members`
+fn lookup<E: 'static>(members: &Rc<dyn Explicit<E>>) -> Condition<E> {
+    let members = Rc::clone(members);
+    Rc::new(move |x: &E| members.contains(x))
+}
+
+/// The members of a finite [`AlgaeSet`], stored as plain data.
+///
+/// Conditions are closures and can't be persisted, so a snapshot keeps the
+/// members found among some candidates instead. With the `serde` feature
+/// enabled, snapshots implement `Serialize` and `Deserialize`.
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::algaeset::AlgaeSet;
+///
+/// let evens = AlgaeSet::<u8>::of(|x| x % 2 == 0);
+/// let candidates: Vec<u8> = (0..10).collect();
+/// let snapshot = evens.to_finite_snapshot(&candidates);
+/// assert!(snapshot.members == vec![0, 2, 4, 6, 8]);
+///
+/// let restored = AlgaeSet::from_snapshot(&snapshot);
+/// assert!(restored.has(&4) && !restored.has(&5) && !restored.has(&12));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiniteSetSnapshot<E> {
+    pub members: Vec<E>,
+}
+
+/// A type with finitely many values, all of which can be listed.
+///
+/// Sets over an [`Enumerable`] type are compared as true sets by `==`,
+/// checking membership of every value of the type.
+pub trait Enumerable: Sized {
+    /// Returns every value of the type
+    fn elements() -> Vec<Self>;
+}
+
+impl Enumerable for bool {
+    fn elements() -> Vec<Self> {
+        vec![false, true]
+    }
+}
+
+impl Enumerable for u8 {
+    fn elements() -> Vec<Self> {
+        (u8::MIN..=u8::MAX).collect()
+    }
+}
+
+impl Enumerable for i8 {
+    fn elements() -> Vec<Self> {
+        (i8::MIN..=i8::MAX).collect()
+    }
+}
+
+impl<E: PartialEq + Clone + 'static> FromIterator<E> for AlgaeSet<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let members: Vec<E> = iter.into_iter().collect();
+        Self::of(move |x: &E| members.contains(x))
+    }
+}
+
+impl<E> fmt::Debug for AlgaeSet<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        // Conditions are opaque closures, so only their counts are shown
+        f.debug_struct("AlgaeSet")
+            .field("pos_conditions", &self.pos_conditions.len())
+            .field("neg_conditions", &self.neg_conditions.len())
+            .field("complemented", &self.complemented)
+            .finish()
+    }
+}
+
+impl<E: Enumerable + Clone> PartialEq for AlgaeSet<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.equals(other, &E::elements())
+    }
+}
+
+impl<E: Clone> Clone for AlgaeSet<E> {
+    fn clone(&self) -> Self {
+        // Conditions and hashed elements are shared rather than copied;
+        // `add` copies the hashed elements before changing them whenever
+        // another set or condition still holds them
+        Self {
+            pos_conditions: self.pos_conditions.clone(),
+            neg_conditions: self.neg_conditions.clone(),
+            explicit: self.explicit.clone(),
+            complemented: self.complemented,
+            provenance: self.provenance.clone(),
+        }
+    }
 }
 
 impl<E> AlgaeSet<E> {
     /// Returns an AlgaeSet defined by a `Vec` of conditions
-    pub fn new(pos_conditions: Vec<Box<dyn Fn(E) -> bool>>) -> Self {
+    pub fn new(pos_conditions: Vec<BoxedCondition<E>>) -> Self {
         Self {
-            pos_conditions,
+            pos_conditions: pos_conditions.into_iter().map(Rc::from).collect(),
             neg_conditions: vec![],
+            explicit: None,
+            complemented: false,
+            provenance: Provenance::Atom(String::from("set")),
         }
     }
 
     /// Returns an AlgaeSet defined by a single condition
-    pub fn mono(condition: Box<dyn Fn(E) -> bool>) -> Self {
+    pub fn mono(condition: BoxedCondition<E>) -> Self {
         Self::new(vec![condition])
     }
 
+    /// Returns an AlgaeSet defined by a single unboxed condition
+    pub fn of(condition: impl Fn(&E) -> bool + 'static) -> Self {
+        Self::mono(Box::new(condition))
+    }
+
+    /// Returns an AlgaeSet defined by a `Vec` of unboxed conditions
+    pub fn of_many<F: Fn(&E) -> bool + 'static>(conditions: Vec<F>) -> Self {
+        Self::new(
+            conditions
+                .into_iter()
+                .map(|c| Box::new(c) as BoxedCondition<E>)
+                .collect(),
+        )
+    }
+
     /// Returns an AlgaeSet containing all members of the type `E`
     pub fn all() -> Self {
         Self {
-            pos_conditions: vec![Box::new(|_x: E| true)],
+            pos_conditions: vec![Rc::new(|_x: &E| true)],
             neg_conditions: vec![],
+            explicit: None,
+            complemented: false,
+            provenance: Provenance::Atom(String::from("all")),
+        }
+    }
+
+    /// Returns an empty AlgaeSet with room for `n` conditions of each kind
+    ///
+    /// Elements and sets added afterwards don't reallocate the condition
+    /// lists until more than `n` of either kind have been added.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            pos_conditions: Vec::with_capacity(n),
+            neg_conditions: Vec::with_capacity(n),
+            explicit: None,
+            complemented: false,
+            provenance: Provenance::Atom(String::from("set")),
         }
     }
+
+    /// Releases any unused room in the given set's condition lists
+    pub fn shrink_to_fit(&mut self) {
+        self.pos_conditions.shrink_to_fit();
+        self.neg_conditions.shrink_to_fit();
+    }
+
+    /// Returns the given set, named `name` in its provenance
+    pub fn named(mut self, name: &str) -> Self {
+        self.provenance = Provenance::Atom(String::from(name));
+        self
+    }
+
+    /// Returns the record of set operations used to build the given set
+    pub fn provenance(&self) -> &Provenance<E> {
+        &self.provenance
+    }
+
+    /// Returns whether a negative condition, added after `since` positive
+    /// conditions, excludes `element`
+    ///
+    /// A negative condition is overridden by any positive condition added
+    /// after it, so later inclusions win over earlier exclusions.
+    fn excludes(&self, (condition, since): &(Condition<E>, usize), element: &E) -> bool {
+        (condition)(element) && !self.pos_conditions[*since..].iter().any(|c| (c)(element))
+    }
+
+    fn exclude(&mut self, condition: Condition<E>) {
+        self.neg_conditions.push((condition, self.pos_conditions.len()));
+    }
+
+    fn record(&mut self, operation: impl FnOnce(Box<Provenance<E>>) -> Provenance<E>) {
+        let previous = std::mem::replace(&mut self.provenance, Provenance::Atom(String::new()));
+        self.provenance = operation(Box::new(previous));
+    }
+
+    /// Replaces the given set with its complement in `E`
+    ///
+    /// Complementation only flips how the existing conditions are read, so
+    /// it never adds conditions to the set.
+    pub fn complement(&mut self) {
+        self.complemented = !self.complemented;
+        self.record(Provenance::Complement);
+    }
+
+    /// Returns the complement of the given set in `E`
+    pub fn complemented(mut self) -> Self {
+        self.complement();
+        self
+    }
+}
+
+impl<E: Eq + Hash + Clone + 'static> AlgaeSet<E> {
+    /// Returns an AlgaeSet containing exactly the given `elements`
+    ///
+    /// The elements are stored in a single hashed lookup, so membership
+    /// queries take constant time regardless of how many elements are given.
+    /// Elements later given to [`add`](fn@AlgaeSet::add) join the same
+    /// lookup where possible.
+    pub fn from_elements(elements: &[E]) -> Self {
+        let members: HashSet<E> = elements.iter().cloned().collect();
+        let members: Rc<dyn Explicit<E>> = Rc::new(members);
+        let mut set = Self::new(vec![]);
+        set.pos_conditions.push(lookup(&members));
+        set.explicit = Some((0, members));
+        set
+    }
+
+    /// Returns the explicit set of the members stored in `snapshot`
+    pub fn from_snapshot(snapshot: &FiniteSetSnapshot<E>) -> Self {
+        Self::from_elements(&snapshot.members)
+    }
+
+    /// Returns the members of `sample` as an explicit set
+    ///
+    /// Two sets agreeing over `sample` canonicalize to the same explicit set,
+    /// however many conditions were used to build them.
+    pub fn canonical_over(&self, sample: &[E]) -> Self {
+        let members: Vec<E> = sample.iter().filter(|e| self.has(e)).cloned().collect();
+        Self::from_elements(&members)
+    }
+
+    /// Collapses the given set's conditions into a single lookup of its members in `domain_sample`
+    ///
+    /// This bounds the cost of `has` after long chains of set operations,
+    /// but only preserves membership over `domain_sample`: every element
+    /// outside it is dropped from the set. The set's provenance is kept.
+    pub fn compact(&mut self, domain_sample: &[E]) {
+        let compacted = self.canonical_over(domain_sample);
+        self.pos_conditions = compacted.pos_conditions;
+        self.neg_conditions = compacted.neg_conditions;
+        self.explicit = compacted.explicit;
+        self.complemented = false;
+    }
+
+    /// Returns every subset of `universe` as an explicit set
+    ///
+    /// The `i`th subset holds the elements of `universe` at the set bits of
+    /// `i`, so each subset comes after all of its own subsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `universe` has more than 16 elements, since the power set
+    /// grows as 2ⁿ.
+    pub fn subset_lattice(universe: &[E]) -> Vec<Self> {
+        assert!(universe.len() <= 16);
+        (0..1usize << universe.len())
+            .map(|bits| {
+                let members: Vec<E> = universe
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| bits & (1 << i) != 0)
+                    .map(|(_, e)| e.clone())
+                    .collect();
+                Self::from_elements(&members)
+            })
+            .collect()
+    }
+}
+
+impl<A: Clone + 'static, B: Clone + 'static> AlgaeSet<(A, B)> {
+    /// Returns the Cartesian product of `a` and `b`
+    ///
+    /// Both sets are moved into the product's single condition, which is why
+    /// their carriers must be `'static`; clone either set first to keep using
+    /// it separately. Later changes to such a clone don't affect the product.
+    pub fn product(a: AlgaeSet<A>, b: AlgaeSet<B>) -> Self {
+        Self::of(move |(x, y): &(A, B)| a.has(x) && b.has(y)).named("product")
+    }
 }
 
-impl<E: Copy + Clone> AlgaeSet<E> {
+impl<E: PartialOrd + Copy + 'static> AlgaeSet<E> {
+    /// Returns the interval `[lo, hi]`, including both endpoints
+    pub fn closed_interval(lo: E, hi: E) -> Self {
+        Self::of(move |x: &E| lo <= *x && *x <= hi)
+    }
+
+    /// Returns the interval `(lo, hi)`, excluding both endpoints
+    pub fn open_interval(lo: E, hi: E) -> Self {
+        Self::of(move |x: &E| lo < *x && *x < hi)
+    }
+
+    /// Returns the interval `[lo, hi)`, including `lo` but excluding `hi`
+    pub fn half_open(lo: E, hi: E) -> Self {
+        Self::of(move |x: &E| lo <= *x && *x < hi)
+    }
+
+    /// Returns whether the given set has no holes among the elements of `sample`
+    ///
+    /// A set is convex over `sample` when every element of `sample` lying
+    /// between two members is itself a member, as is the case for intervals.
+    pub fn is_convex_over(&self, sample: &[E]) -> bool {
+        let members: Vec<E> = sample.iter().filter(|e| self.has(e)).copied().collect();
+        sample.iter().filter(|b| !self.has(b)).all(|b| {
+            !(members.iter().any(|a| a <= b) && members.iter().any(|c| b <= c))
+        })
+    }
+}
+
+impl<E: Ord + Clone> AlgaeSet<E> {
+    /// Returns the members of `sample` in sorted order, without duplicates
+    pub fn to_sorted_vec(&self, sample: &[E]) -> Vec<E> {
+        let mut members: Vec<E> = sample.iter().filter(|e| self.has(e)).cloned().collect();
+        members.sort();
+        members.dedup();
+        members
+    }
+}
+
+impl<E: Clone> AlgaeSet<E> {
     /// Returns whether or not `element` is in the given set
-    pub fn has(&self, element: E) -> bool {
-        if self.neg_conditions.iter().any(|c| (c)(element)) {
+    pub fn has(&self, element: &E) -> bool {
+        self.satisfies_conditions(element) != self.complemented
+    }
+
+    fn satisfies_conditions(&self, element: &E) -> bool {
+        if self.neg_conditions.iter().any(|n| self.excludes(n, element)) {
             return false;
         }
-        return self.pos_conditions.iter().any(|c| (c)(element));
+        self.pos_conditions.iter().any(|c| (c)(element))
+    }
+
+    /// Returns whether or not `element` is in the given set, along with the
+    /// index of the condition deciding it
+    ///
+    /// Conditions are indexed in the order `has` evaluates them: negative
+    /// conditions first, then positive ones. If no condition matches
+    /// `element`, the index is one past the last condition.
+    pub fn has_explained(&self, element: &E) -> (bool, usize) {
+        if let Some(i) = self.neg_conditions.iter().position(|n| self.excludes(n, element)) {
+            return (self.complemented, i);
+        }
+        let negatives = self.neg_conditions.len();
+        match self.pos_conditions.iter().position(|c| (c)(element)) {
+            Some(i) => (!self.complemented, negatives + i),
+            None => (self.complemented, negatives + self.pos_conditions.len()),
+        }
+    }
+
+    /// Returns whether or not `(left, right)` is in the product of `self` and `other`
+    pub fn has_pair<B: Clone>(&self, other: &AlgaeSet<B>, left: &E, right: &B) -> bool {
+        self.has(left) && other.has(right)
+    }
+
+    /// Returns the members of the given set among `candidates`, in order
+    pub fn elements_from<I: IntoIterator<Item = E>>(&self, candidates: I) -> Vec<E> {
+        candidates.into_iter().filter(|e| self.has(e)).collect()
+    }
+
+    /// Returns a snapshot of the members of the given set among `candidates`
+    ///
+    /// Only `candidates` are stored, so a set rebuilt by
+    /// [`from_snapshot`](fn@AlgaeSet::from_snapshot) agrees with the given
+    /// set over them and has no other members.
+    pub fn to_finite_snapshot(&self, candidates: &[E]) -> FiniteSetSnapshot<E> {
+        FiniteSetSnapshot {
+            members: self.elements_from(candidates.iter().cloned()),
+        }
+    }
+
+    /// Returns an iterator over the members of the given set among `candidates`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algae_rs::algaeset::AlgaeSet;
+    ///
+    /// let Z2 = AlgaeSet::<i32>::of(|x| x % 2 == *x);
+    /// let members: Vec<i32> = Z2.iter_over(0..10).collect();
+    /// assert!(members == vec![0, 1]);
+    /// ```
+    pub fn iter_over<'b>(
+        &'b self,
+        candidates: impl Iterator<Item = E> + 'b,
+    ) -> impl Iterator<Item = E> + 'b {
+        candidates.filter(move |e| self.has(e))
+    }
+
+    /// Returns the elements of `sample` only in `self` and those only in `other`
+    pub fn diff_over(&self, other: &Self, sample: &[E]) -> (Vec<E>, Vec<E>) {
+        let only_in_self = sample
+            .iter()
+            .filter(|e| self.has(e) && !other.has(e))
+            .cloned()
+            .collect();
+        let only_in_other = sample
+            .iter()
+            .filter(|e| other.has(e) && !self.has(e))
+            .cloned()
+            .collect();
+        (only_in_self, only_in_other)
+    }
+
+    /// Returns an element of `sample` in both `self` and `other`, if any
+    pub fn common_element(&self, other: &Self, sample: &[E]) -> Option<E> {
+        sample
+            .iter()
+            .find(|e| self.has(e) && other.has(e))
+            .cloned()
+    }
+
+    /// Returns the number of distinct members of `sample` in the given set
+    pub fn count_members(&self, sample: &[E]) -> usize
+    where
+        E: PartialEq,
+    {
+        let mut members: Vec<E> = vec![];
+        for e in sample {
+            if self.has(e) && !members.contains(e) {
+                members.push(e.clone());
+            }
+        }
+        members.len()
+    }
+
+    /// Returns whether every member of the given set in `sample` satisfies `p`
+    pub fn for_all_members<P: Fn(&E) -> bool>(&self, sample: &[E], p: P) -> bool {
+        sample.iter().filter(|e| self.has(e)).all(p)
+    }
+
+    /// Returns whether some member of the given set in `sample` satisfies `p`
+    pub fn exists_member<P: Fn(&E) -> bool>(&self, sample: &[E], p: P) -> bool {
+        sample.iter().filter(|e| self.has(e)).any(p)
+    }
+
+    /// Returns whether no element of `domain_sample` is in both `self` and `other`
+    pub fn is_disjoint_from(&self, other: &Self, domain_sample: &[E]) -> bool {
+        self.common_element(other, domain_sample).is_none()
+    }
+
+    /// Returns whether the given set contains exactly one of `a` and `b`
+    pub fn separates(&self, a: &E, b: &E) -> bool {
+        self.has(a) != self.has(b)
+    }
+
+    /// Returns the elements of `sample` lying in every one of `sets`
+    ///
+    /// This realizes the intersection of a whole family of sets over a finite
+    /// sample; an empty family keeps all of `sample`.
+    pub fn intersect_over(sets: &[Self], sample: &[E]) -> Vec<E> {
+        sample
+            .iter()
+            .filter(|e| sets.iter().all(|set| set.has(e)))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns whether every one of `elements` is in the given set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algae_rs::algaeset::AlgaeSet;
+    ///
+    /// let Z2 = AlgaeSet::<i32>::of(|x| x % 2 == *x);
+    /// assert!(Z2.contains_all(&[0, 1]));
+    /// assert!(!Z2.contains_all(&[0, 1, 2]));
+    /// ```
+    pub fn contains_all(&self, elements: &[E]) -> bool {
+        elements.iter().all(|e| self.has(e))
+    }
+
+    /// Returns whether any of `elements` is in the given set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algae_rs::algaeset::AlgaeSet;
+    ///
+    /// let Z2 = AlgaeSet::<i32>::of(|x| x % 2 == *x);
+    /// assert!(Z2.contains_any(&[1, 2, 3]));
+    /// assert!(!Z2.contains_any(&[2, 3]));
+    /// ```
+    pub fn contains_any(&self, elements: &[E]) -> bool {
+        elements.iter().any(|e| self.has(e))
+    }
+
+    /// Returns whether every member of `other` in `sample` is also in `self`
+    pub fn contains_subset(&self, other: &Self, sample: &[E]) -> bool {
+        sample.iter().filter(|e| other.has(e)).all(|e| self.has(e))
+    }
+
+    /// Returns whether every member of the given set in `domain_sample` is also in `other`
+    pub fn is_subset_of(&self, other: &Self, domain_sample: &[E]) -> bool {
+        other.contains_subset(self, domain_sample)
+    }
+
+    /// Returns whether `self` and `other` agree on every element of `domain_sample`
+    ///
+    /// This is equality relative to the sample only: sets agreeing over
+    /// `domain_sample` may still differ elsewhere in `E`.
+    pub fn equals(&self, other: &Self, domain_sample: &[E]) -> bool {
+        self.is_subset_of(other, domain_sample) && other.is_subset_of(self, domain_sample)
+    }
+
+    /// Returns whether `f` maps every member of `sample` back into the given set
+    pub fn closed_under_unary(&self, f: &dyn Fn(&E) -> E, sample: &[E]) -> bool {
+        sample
+            .iter()
+            .filter(|e| self.has(e))
+            .all(|e| self.has(&(f)(e)))
+    }
+
+    /// Returns a closure deciding membership in the given set
+    pub fn predicate(&self) -> impl Fn(&E) -> bool + '_ {
+        move |element: &E| self.has(element)
+    }
+
+    /// Returns the set of all `u` whose image under `g` is in the given set
+    ///
+    /// This pulls the given set back along `g`, so mapping a set into
+    /// another element type only needs a map back from that type.
+    pub fn contravariant_map<U: 'static>(self, g: Box<dyn Fn(&U) -> E>) -> AlgaeSet<U>
+    where
+        E: 'static,
+    {
+        AlgaeSet::of(move |u: &U| self.has(&(g)(u)))
     }
 }
 
-impl<E: PartialEq + Copy + Clone + 'static> AlgaeSet<E> {
+impl<E: PartialEq + Clone + 'static> AlgaeSet<E> {
+    /// Returns an AlgaeSet containing only `element`
+    pub fn singleton(element: E) -> Self {
+        Self::of(move |x: &E| *x == element)
+    }
+
+    /// Returns a singleton set for each distinct member of `sample` in the given set
+    pub fn singletons_over(&self, sample: &[E]) -> Vec<Self> {
+        let mut members: Vec<E> = vec![];
+        for e in sample {
+            if self.has(e) && !members.contains(e) {
+                members.push(e.clone());
+            }
+        }
+        members.into_iter().map(Self::singleton).collect()
+    }
+
+    /// Returns whether each Boolean-algebra law holds for the given sets over `universe`
+    ///
+    /// The sets are built fresh from `a`, `b`, and `c` for each expression,
+    /// and the identity, complement, distributivity, and De Morgan laws are
+    /// each checked elementwise over `universe`.
+    pub fn boolean_algebra_laws(
+        a: &dyn Fn() -> Self,
+        b: &dyn Fn() -> Self,
+        c: &dyn Fn() -> Self,
+        universe: &[E],
+    ) -> Vec<(&'static str, bool)> {
+        let union = |mut left: Self, right: Self| {
+            left.or(right);
+            left
+        };
+        let intersection = |mut left: Self, right: Self| {
+            left.and(right);
+            left
+        };
+        let complement = |mut set: Self| {
+            set.complement();
+            set
+        };
+        let agree = |left: Self, right: Self| {
+            universe.iter().all(|e| left.has(e) == right.has(e))
+        };
+
+        let identity = agree(union(a(), Self::new(vec![])), a())
+            && agree(intersection(a(), Self::all()), a());
+        let complements = agree(union(a(), complement(a())), Self::all())
+            && agree(intersection(a(), complement(a())), Self::new(vec![]));
+        let distributivity = agree(
+            intersection(a(), union(b(), c())),
+            union(intersection(a(), b()), intersection(a(), c())),
+        ) && agree(
+            union(a(), intersection(b(), c())),
+            intersection(union(a(), b()), union(a(), c())),
+        );
+        let de_morgan = agree(
+            complement(union(a(), b())),
+            intersection(complement(a()), complement(b())),
+        ) && agree(
+            complement(intersection(a(), b())),
+            union(complement(a()), complement(b())),
+        );
+        vec![
+            ("identity", identity),
+            ("complement", complements),
+            ("distributivity", distributivity),
+            ("de morgan", de_morgan),
+        ]
+    }
+
     /// Adds `element` to the given set
-    pub fn add(&mut self, element: E) {
-        self.neg_conditions.retain(|c| !(c)(element));
-        self.pos_conditions.push(Box::new(move |x: E| x == element))
+    pub fn add(&mut self, element: &E) {
+        if self.complemented {
+            self.exclude_element(element.clone());
+        } else if !self.insert_explicitly(element) {
+            self.include_element(element.clone());
+        }
+        self.record(|set| Provenance::Insertion(set, element.clone()));
     }
 
     /// Removes `element` from the given set
-    pub fn remove(&mut self, element: E) {
-        self.pos_conditions.retain(|c| (c)(element));
-        self.neg_conditions.push(Box::new(move |x: E| x == element))
+    pub fn remove(&mut self, element: &E) {
+        if self.complemented {
+            self.include_element(element.clone());
+        } else {
+            self.exclude_element(element.clone());
+        }
+        self.record(|set| Provenance::Removal(set, element.clone()));
     }
 
     /// Adds all elements from `other` to `self`
-    pub fn or(&mut self, other: Self) {
-        self.pos_conditions.push(Box::new(move |x: E| other.has(x)));
+    pub fn or(&mut self, mut other: Self) {
+        let other_provenance = other.take_provenance();
+        self.record(|set| Provenance::Union(set, other_provenance));
+        // By De Morgan, the union of a complement is the complement of an
+        // intersection with the complement of `other`
+        if self.complemented {
+            self.exclude(Rc::new(move |x: &E| other.has(x)));
+        } else {
+            self.include(Rc::new(move |x: &E| other.has(x)));
+        }
     }
 
     /// Removes all elements from `self` that aren't in `other`
-    pub fn and(&mut self, other: Self) {
-        self.neg_conditions
-            .push(Box::new(move |x: E| !other.has(x)));
+    pub fn and(&mut self, mut other: Self) {
+        let other_provenance = other.take_provenance();
+        self.record(|set| Provenance::Intersection(set, other_provenance));
+        // By De Morgan, the intersection of a complement is the complement of
+        // a union with the complement of `other`
+        if self.complemented {
+            self.include(Rc::new(move |x: &E| !other.has(x)));
+        } else {
+            self.exclude(Rc::new(move |x: &E| !other.has(x)));
+        }
+    }
+
+    /// Returns the given set, restricted to elements satisfying `condition`
+    pub fn filter(mut self, condition: BoxedCondition<E>) -> Self {
+        self.and(Self::mono(condition));
+        self
+    }
+
+    /// Removes all elements of `other` from `self`
+    pub fn minus(&mut self, mut other: Self) {
+        let other_provenance = other.take_provenance();
+        self.record(|set| Provenance::Difference(set, other_provenance));
+        // By De Morgan, removing from a complement is the complement of a
+        // union with `other`
+        if self.complemented {
+            self.include(Rc::new(move |x: &E| other.has(x)));
+        } else {
+            self.exclude(Rc::new(move |x: &E| other.has(x)));
+        }
+    }
+
+    /// Keeps the elements in exactly one of `self` and `other`
+    pub fn symmetric_difference(&mut self, other: Self) {
+        // (A \ B) ∪ (B \ A), sharing the conditions of each set between the
+        // two differences
+        let mut only_in_other = other.clone();
+        only_in_other.minus(self.clone());
+        self.minus(other);
+        self.or(only_in_other);
+    }
+
+    /// Adds all elements from `other` to `self`, leaving `other` in place
+    pub fn or_ref(&mut self, other: &Self) {
+        self.or(other.clone());
+    }
+
+    /// Removes all elements from `self` that aren't in `other`, leaving `other` in place
+    pub fn and_ref(&mut self, other: &Self) {
+        self.and(other.clone());
+    }
+
+    /// Removes all elements of `other` from `self`, leaving `other` in place
+    pub fn difference_ref(&mut self, other: &Self) {
+        self.minus(other.clone());
+    }
+
+    /// Keeps the elements in exactly one of `self` and `other`, leaving `other` in place
+    pub fn symmetric_difference_ref(&mut self, other: &Self) {
+        self.symmetric_difference(other.clone());
+    }
+
+    fn take_provenance(&mut self) -> Box<Provenance<E>> {
+        Box::new(std::mem::replace(
+            &mut self.provenance,
+            Provenance::Atom(String::new()),
+        ))
+    }
+
+    fn include(&mut self, condition: Condition<E>) {
+        // Negative conditions already in place are relaxed by `condition`
+        // through `excludes`, which consults every later positive condition
+        self.pos_conditions.push(condition);
+    }
+
+    /// Inserts `element` into the set's hashed elements, if it has any
+    ///
+    /// An exclusion added after the hashed elements would still override
+    /// them, so the element is then included through its own condition
+    /// instead. The hashed elements are copied on write, leaving any other
+    /// set or condition holding them unchanged.
+    fn insert_explicitly(&mut self, element: &E) -> bool {
+        let Some((index, members)) = &mut self.explicit else {
+            return false;
+        };
+        if self.neg_conditions.iter().any(|(_, since)| since > index) {
+            return false;
+        }
+        // Release this set's own lookup, so that only other holders of the
+        // hashed elements keep them shared
+        self.pos_conditions[*index] = Rc::new(|_: &E| false);
+        if Rc::get_mut(members).is_none() {
+            *members = members.copied();
+        }
+        if let Some(members) = Rc::get_mut(members) {
+            members.insert(element.clone());
+        }
+        self.pos_conditions[*index] = lookup(members);
+        true
+    }
+
+    fn include_element(&mut self, element: E) {
+        self.include(Rc::new(move |x: &E| *x == element))
+    }
+
+    fn exclude_element(&mut self, element: E) {
+        self.exclude(Rc::new(move |x: &E| *x == element))
     }
 }
 
@@ -112,219 +898,865 @@ mod tests {
         #[test]
         fn has_element() {
             let REALS = AlgaeSet::<Real>::all();
-            assert!(REALS.has(Real::UInt(12)));
-            assert!(REALS.has(Real::SInt(-42)));
-            assert!(REALS.has(Real::Float(-34.2)));
+            assert!(REALS.has(&Real::UInt(12)));
+            assert!(REALS.has(&Real::SInt(-42)));
+            assert!(REALS.has(&Real::Float(-34.2)));
         }
 
         #[test]
         fn remove_element() {
             let mut REALS = AlgaeSet::<Real>::all();
-            REALS.remove(Real::Float(23.1));
-            assert!(REALS.has(Real::Float(23.2)));
-            assert!(!REALS.has(Real::Float(23.1)));
+            REALS.remove(&Real::Float(23.1));
+            assert!(REALS.has(&Real::Float(23.2)));
+            assert!(!REALS.has(&Real::Float(23.1)));
         }
 
         #[test]
         fn add_after_remove() {
             let mut REALS = AlgaeSet::<Real>::all();
-            REALS.remove(Real::Float(32.1));
-            assert!(!REALS.has(Real::Float(32.1)));
-            REALS.add(Real::Float(32.1));
-            assert!(REALS.has(Real::Float(32.1)));
+            REALS.remove(&Real::Float(32.1));
+            assert!(!REALS.has(&Real::Float(32.1)));
+            REALS.add(&Real::Float(32.1));
+            assert!(REALS.has(&Real::Float(32.1)));
         }
 
         #[test]
         fn remove_after_add_after_remove() {
             let mut REALS = AlgaeSet::<Real>::all();
-            assert!(REALS.has(Real::Float(32.1)));
-            REALS.remove(Real::Float(32.1));
-            assert!(!REALS.has(Real::Float(32.1)));
-            REALS.add(Real::Float(32.1));
-            assert!(REALS.has(Real::Float(32.1)));
-            REALS.remove(Real::Float(32.1));
-            assert!(!REALS.has(Real::Float(32.1)));
+            assert!(REALS.has(&Real::Float(32.1)));
+            REALS.remove(&Real::Float(32.1));
+            assert!(!REALS.has(&Real::Float(32.1)));
+            REALS.add(&Real::Float(32.1));
+            assert!(REALS.has(&Real::Float(32.1)));
+            REALS.remove(&Real::Float(32.1));
+            assert!(!REALS.has(&Real::Float(32.1)));
+        }
+
+        #[test]
+        fn symmetric_difference_with_superset() {
+            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
+                Real::UInt(_) => false,
+                Real::SInt(_) => false,
+                Real::Float(_) => true,
+            }));
+            let REALS = AlgaeSet::<Real>::all();
+            FLOATS.symmetric_difference(REALS);
+            assert!(!FLOATS.has(&Real::Float(1.5)));
+            assert!(FLOATS.has(&Real::UInt(3)));
+            assert!(FLOATS.has(&Real::SInt(-3)));
+        }
+
+        #[test]
+        fn intersection_with_all_equals_floats() {
+            let FLOATS = || {
+                AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
+                    Real::UInt(_) => false,
+                    Real::SInt(_) => false,
+                    Real::Float(_) => true,
+                }))
+            };
+            let sample = [Real::UInt(2), Real::SInt(-7), Real::Float(0.5), Real::Float(-3.0)];
+            let mut REALS = AlgaeSet::<Real>::all();
+            assert!(!REALS.equals(&FLOATS(), &sample));
+            REALS.and(FLOATS());
+            assert!(REALS.equals(&FLOATS(), &sample));
+            assert!(REALS.equals(&FLOATS(), &[Real::Float(0.5), Real::Float(1.5)]));
+        }
+
+        #[test]
+        fn uints_and_floats_are_disjoint() {
+            let UINTS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
+                Real::UInt(_) => true,
+                Real::SInt(_) => false,
+                Real::Float(_) => false,
+            }));
+            let FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
+                Real::UInt(_) => false,
+                Real::SInt(_) => false,
+                Real::Float(_) => true,
+            }));
+            let sample = [Real::UInt(2), Real::SInt(-7), Real::Float(2.0)];
+            assert!(UINTS.is_disjoint_from(&FLOATS, &sample));
+            assert!(FLOATS.is_disjoint_from(&UINTS, &sample));
+        }
+
+        #[test]
+        fn union_after_remove() {
+            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
+                Real::UInt(_) => false,
+                Real::SInt(_) => false,
+                Real::Float(_) => true,
+            }));
+            FLOATS.remove(&Real::Float(1.5));
+            assert!(!FLOATS.has(&Real::Float(1.5)));
+            FLOATS.or(AlgaeSet::<Real>::mono(Box::new(|&x: &Real| x == Real::Float(1.5))));
+            assert!(FLOATS.has(&Real::Float(1.5)));
+            assert!(FLOATS.has(&Real::Float(2.5)));
+        }
+
+        #[test]
+        fn remove_after_union() {
+            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
+                Real::UInt(_) => false,
+                Real::SInt(_) => false,
+                Real::Float(_) => true,
+            }));
+            FLOATS.or(AlgaeSet::<Real>::mono(Box::new(|&x: &Real| x == Real::UInt(1))));
+            FLOATS.remove(&Real::UInt(1));
+            assert!(!FLOATS.has(&Real::UInt(1)));
+        }
+
+        #[test]
+        fn add_after_intersection() {
+            let mut REALS = AlgaeSet::<Real>::all();
+            REALS.and(AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
+                Real::UInt(_) => false,
+                Real::SInt(_) => false,
+                Real::Float(_) => true,
+            })));
+            REALS.add(&Real::UInt(3));
+            assert!(REALS.has(&Real::UInt(3)));
+            assert!(!REALS.has(&Real::UInt(4)));
+            assert!(REALS.has(&Real::Float(4.0)));
         }
 
         #[test]
         fn overlapping_union() {
             let REALS = AlgaeSet::<Real>::all();
-            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => false,
                 Real::SInt(_) => false,
                 Real::Float(_) => true,
             }));
-            assert!(!FLOATS.has(Real::UInt(12)));
+            assert!(!FLOATS.has(&Real::UInt(12)));
             FLOATS.or(REALS);
-            assert!(FLOATS.has(Real::UInt(12)));
+            assert!(FLOATS.has(&Real::UInt(12)));
         }
 
         #[test]
         fn encompassing_union() {
             let mut REALS = AlgaeSet::<Real>::all();
-            let FLOATS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => false,
                 Real::SInt(_) => false,
                 Real::Float(_) => true,
             }));
             REALS.or(FLOATS);
-            assert!(REALS.has(Real::Float(12.0)));
-            assert!(REALS.has(Real::UInt(12)));
-            assert!(REALS.has(Real::SInt(-12)));
+            assert!(REALS.has(&Real::Float(12.0)));
+            assert!(REALS.has(&Real::UInt(12)));
+            assert!(REALS.has(&Real::SInt(-12)));
         }
 
         #[test]
         fn disjoint_union() {
-            let UINTS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let UINTS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => true,
                 Real::SInt(_) => false,
                 Real::Float(_) => false,
             }));
-            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => false,
                 Real::SInt(_) => false,
                 Real::Float(_) => true,
             }));
-            assert!(FLOATS.has(Real::Float(12.0)));
-            assert!(!FLOATS.has(Real::UInt(12)));
+            assert!(FLOATS.has(&Real::Float(12.0)));
+            assert!(!FLOATS.has(&Real::UInt(12)));
             FLOATS.or(UINTS);
-            assert!(FLOATS.has(Real::Float(12.0)));
-            assert!(FLOATS.has(Real::UInt(12)));
+            assert!(FLOATS.has(&Real::Float(12.0)));
+            assert!(FLOATS.has(&Real::UInt(12)));
         }
 
         #[test]
         fn overlapping_intersection() {
             let REALS = AlgaeSet::<Real>::all();
-            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => false,
                 Real::SInt(_) => false,
                 Real::Float(_) => true,
             }));
-            assert!(!FLOATS.has(Real::UInt(12)));
+            assert!(!FLOATS.has(&Real::UInt(12)));
             FLOATS.and(REALS);
-            assert!(!FLOATS.has(Real::UInt(12)));
+            assert!(!FLOATS.has(&Real::UInt(12)));
         }
 
         #[test]
         fn encompassing_intersection() {
             let mut REALS = AlgaeSet::<Real>::all();
-            let FLOATS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => false,
                 Real::SInt(_) => false,
                 Real::Float(_) => true,
             }));
-            assert!(REALS.has(Real::UInt(12)));
-            assert!(REALS.has(Real::SInt(-12)));
-            assert!(REALS.has(Real::Float(12.0)));
+            assert!(REALS.has(&Real::UInt(12)));
+            assert!(REALS.has(&Real::SInt(-12)));
+            assert!(REALS.has(&Real::Float(12.0)));
             REALS.and(FLOATS);
-            assert!(REALS.has(Real::Float(12.0)));
-            assert!(!REALS.has(Real::UInt(12)));
-            assert!(!REALS.has(Real::SInt(-12)));
+            assert!(REALS.has(&Real::Float(12.0)));
+            assert!(!REALS.has(&Real::UInt(12)));
+            assert!(!REALS.has(&Real::SInt(-12)));
         }
 
         #[test]
         fn disjoint_intersection() {
-            let UINTS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let UINTS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => true,
                 Real::SInt(_) => false,
                 Real::Float(_) => false,
             }));
-            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|x: Real| match x {
+            let mut FLOATS = AlgaeSet::<Real>::mono(Box::new(|&x: &Real| match x {
                 Real::UInt(_) => false,
                 Real::SInt(_) => false,
                 Real::Float(_) => true,
             }));
-            assert!(FLOATS.has(Real::Float(12.0)));
-            assert!(!FLOATS.has(Real::UInt(12)));
+            assert!(FLOATS.has(&Real::Float(12.0)));
+            assert!(!FLOATS.has(&Real::UInt(12)));
             FLOATS.and(UINTS);
-            assert!(!FLOATS.has(Real::Float(12.0)));
-            assert!(!FLOATS.has(Real::UInt(12)));
+            assert!(!FLOATS.has(&Real::Float(12.0)));
+            assert!(!FLOATS.has(&Real::UInt(12)));
+        }
+
+        #[test]
+        fn words_over_alphabet() {
+            let mut words = AlgaeSet::<String>::of(|w| w.chars().all(|c| "ab".contains(c)));
+            let palindromes = AlgaeSet::<String>::of(|w| w.chars().eq(w.chars().rev()));
+            assert!(words.has(&String::from("abab")));
+            assert!(!words.has(&String::from("abc")));
+            words.add(&String::from("c"));
+            words.remove(&String::from("ab"));
+            assert!(words.has(&String::from("c")));
+            assert!(!words.has(&String::from("ab")));
+            words.and(palindromes);
+            assert!(words.has(&String::from("abba")));
+            assert!(!words.has(&String::from("abab")));
         }
     }
 
     mod finite_set {
 
         use super::*;
+        use crate::mapping::PropertyType;
 
         #[test]
         fn has_element() {
-            let Z2 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 2 == x));
-            assert!(Z2.has(1));
-            assert!(Z2.has(0));
-            assert!(!Z2.has(2));
-            assert!(!Z2.has(-2));
+            let Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            assert!(Z2.has(&1));
+            assert!(Z2.has(&0));
+            assert!(!Z2.has(&2));
+            assert!(!Z2.has(&-2));
         }
 
         #[test]
         fn add_element() {
-            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 2 == x));
-            assert!(!Z2.has(2));
-            Z2.add(2);
-            assert!(Z2.has(2));
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            assert!(!Z2.has(&2));
+            Z2.add(&2);
+            assert!(Z2.has(&2));
         }
 
         #[test]
         fn remove_element() {
-            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 2 == x));
-            assert!(Z2.has(1));
-            Z2.remove(1);
-            assert!(!Z2.has(1));
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            assert!(Z2.has(&1));
+            Z2.remove(&1);
+            assert!(!Z2.has(&1));
+        }
+
+        #[test]
+        fn remove_keeps_other_conditions() {
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            Z2.or(AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 5)));
+            Z2.remove(&0);
+            assert!(!Z2.has(&0));
+            assert!(Z2.has(&1));
+            assert!(Z2.has(&5));
         }
 
         #[test]
         fn overlapping_union() {
-            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 2 == x));
-            let Z3 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 3 == x));
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
             Z2.or(Z3);
-            assert!(Z2.has(0));
-            assert!(Z2.has(1));
-            assert!(Z2.has(2));
+            assert!(Z2.has(&0));
+            assert!(Z2.has(&1));
+            assert!(Z2.has(&2));
         }
 
         #[test]
         fn encompassing_union() {
-            let Z2 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 2 == x));
-            let mut Z3 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 3 == x));
+            let Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let mut Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
             Z3.or(Z2);
-            assert!(Z3.has(0));
-            assert!(Z3.has(1));
-            assert!(Z3.has(2));
+            assert!(Z3.has(&0));
+            assert!(Z3.has(&1));
+            assert!(Z3.has(&2));
         }
 
         #[test]
         fn disjoint_union() {
-            let mut one = AlgaeSet::<i32>::mono(Box::new(|x: i32| x == 1));
-            let two = AlgaeSet::<i32>::mono(Box::new(|x: i32| x == 2));
+            let mut one = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 1));
+            let two = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 2));
             one.or(two);
-            assert!(one.has(1));
-            assert!(one.has(2));
+            assert!(one.has(&1));
+            assert!(one.has(&2));
         }
 
         #[test]
         fn overlapping_intersection() {
-            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 2 == x));
-            let one = AlgaeSet::<i32>::mono(Box::new(|x: i32| x == 1));
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let one = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 1));
             Z2.and(one);
-            assert!(Z2.has(1));
-            assert!(!Z2.has(0));
+            assert!(Z2.has(&1));
+            assert!(!Z2.has(&0));
         }
 
         #[test]
         fn encompassing_intersection() {
-            let Z2 = AlgaeSet::<i32>::mono(Box::new(|x: i32| x % 2 == x));
-            let mut one = AlgaeSet::<i32>::mono(Box::new(|x: i32| x == 1));
+            let Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let mut one = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 1));
             one.and(Z2);
-            assert!(one.has(1));
-            assert!(!one.has(0));
+            assert!(one.has(&1));
+            assert!(!one.has(&0));
         }
 
         #[test]
         fn disjoint_intersection() {
-            let mut one = AlgaeSet::<i32>::mono(Box::new(|x: i32| x == 1));
-            let two = AlgaeSet::<i32>::mono(Box::new(|x: i32| x == 2));
+            let mut one = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 1));
+            let two = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 2));
             one.and(two);
-            assert!(!one.has(1));
-            assert!(!one.has(2));
+            assert!(!one.has(&1));
+            assert!(!one.has(&2));
+        }
+
+        #[test]
+        fn overlapping_difference() {
+            let mut Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            let Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            Z3.minus(Z2);
+            assert!(!Z3.has(&0));
+            assert!(!Z3.has(&1));
+            assert!(Z3.has(&2));
+        }
+
+        #[test]
+        fn encompassing_difference() {
+            let Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            Z2.minus(Z3);
+            assert!(!Z2.has(&0));
+            assert!(!Z2.has(&1));
+            assert!(!Z2.has(&2));
+        }
+
+        #[test]
+        fn disjoint_difference() {
+            let mut one = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 1));
+            let two = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 2));
+            one.minus(two);
+            assert!(one.has(&1));
+            assert!(!one.has(&2));
+        }
+
+        #[test]
+        fn difference_then_union() {
+            let Z2 = || AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let mut Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            Z3.minus(Z2());
+            Z3.or(Z2());
+            assert!((-3..6).all(|x| Z3.has(&x) == (x % 3 == x)));
+        }
+
+        #[test]
+        fn predicate_filters() {
+            let Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            let in_Z3 = Z3.predicate();
+            let members: Vec<i32> = (0..6).filter(|x| in_Z3(x)).collect();
+            assert!(members == vec![0, 1, 2]);
+        }
+
+        #[test]
+        fn complement_element() {
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            Z2.complement();
+            assert!(!Z2.has(&0));
+            assert!(!Z2.has(&1));
+            assert!(Z2.has(&2));
+            Z2.add(&0);
+            Z2.remove(&2);
+            assert!(Z2.has(&0));
+            assert!(!Z2.has(&2));
+        }
+
+        #[test]
+        fn double_complement() {
+            let mut Z2 = AlgaeSet::<i32>::of(|&x| x % 2 == x);
+            Z2.or(AlgaeSet::of(|&x| x == 5));
+            Z2.and(AlgaeSet::of(|&x| x != 0));
+            let before: Vec<bool> = (-3..8).map(|x| Z2.has(&x)).collect();
+            Z2.complement();
+            assert!((-3..8).all(|x| Z2.has(&x) != before[(x + 3) as usize]));
+            Z2.complement();
+            assert!((-3..8).all(|x| Z2.has(&x) == before[(x + 3) as usize]));
+
+            let empty = AlgaeSet::<i32>::all().complemented();
+            assert!((-3..8).all(|x| !empty.has(&x)));
+            assert!((-3..8).all(|x| empty.clone().complemented().has(&x)));
+        }
+
+        #[test]
+        fn complement_adds_no_conditions() {
+            let mut Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            Z2.and(AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x == 1)));
+            let (pos, neg) = (Z2.pos_conditions.len(), Z2.neg_conditions.len());
+            Z2.complement();
+            assert!(Z2.pos_conditions.len() == pos);
+            assert!(Z2.neg_conditions.len() == neg);
+        }
+
+        #[test]
+        fn complement_obeys_de_morgan() {
+            let Z2 = || AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let Z3 = || AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            let complement = |mut set: AlgaeSet<i32>| {
+                set.complement();
+                set
+            };
+
+            let mut not_union = Z2();
+            not_union.or(Z3());
+            not_union.complement();
+            let mut intersection_of_nots = complement(Z2());
+            intersection_of_nots.and(complement(Z3()));
+
+            let mut not_intersection = Z2();
+            not_intersection.and(Z3());
+            not_intersection.complement();
+            let mut union_of_nots = complement(Z2());
+            union_of_nots.or(complement(Z3()));
+
+            for x in -5..5 {
+                assert!(not_union.has(&x) == intersection_of_nots.has(&x));
+                assert!(not_intersection.has(&x) == union_of_nots.has(&x));
+            }
+        }
+
+        #[test]
+        fn explicit_elements() {
+            let evens: Vec<i32> = (0..100_000).map(|x| 2 * x).collect();
+            let EVENS = AlgaeSet::from_elements(&evens);
+            assert!(EVENS.pos_conditions.len() == 1);
+            assert!(EVENS.neg_conditions.is_empty());
+            assert!(EVENS.has(&0));
+            assert!(EVENS.has(&199_998));
+            assert!(!EVENS.has(&199_999));
+            assert!(!EVENS.has(&200_000));
+        }
+
+        #[test]
+        fn hashed_additions() {
+            let mut set = AlgaeSet::from_elements(&[0, 2, 4]);
+            for x in 0..1_000 {
+                set.add(&(2 * x + 1));
+            }
+            assert!(set.pos_conditions.len() == 1);
+            assert!(set.has(&4) && set.has(&1_999) && !set.has(&6));
+
+            let copy = set.clone();
+            let mut union = AlgaeSet::<i32>::of(|&x| x < 0);
+            union.or_ref(&set);
+            set.add(&6);
+            assert!(set.pos_conditions.len() == 1);
+            assert!(set.has(&6) && !copy.has(&6) && !union.has(&6));
+            assert!(copy.has(&4) && union.has(&4));
+
+            set.remove(&0);
+            set.add(&8);
+            assert!(set.pos_conditions.len() == 2);
+            assert!(!set.has(&0) && set.has(&8));
+        }
+
+        #[test]
+        fn pair_membership() {
+            let Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let vowels = AlgaeSet::<char>::mono(Box::new(|&c: &char| "aeiou".contains(c)));
+            assert!(Z2.has_pair(&vowels, &1, &'a'));
+            assert!(!Z2.has_pair(&vowels, &2, &'a'));
+            assert!(!Z2.has_pair(&vowels, &1, &'b'));
+        }
+
+        #[test]
+        fn diff_over_sample() {
+            let evens = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == 0));
+            let fours = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 4 == 0));
+            let sample: Vec<i32> = (0..10).collect();
+            let (only_evens, only_fours) = evens.diff_over(&fours, &sample);
+            assert!(only_evens == vec![2, 6]);
+            assert!(only_fours.is_empty());
+        }
+
+        #[test]
+        fn common_residue() {
+            let one_mod_three = AlgaeSet::<i32>::of(|&x| x % 3 == 1);
+            let three_mod_four = AlgaeSet::<i32>::of(|&x| x % 4 == 3);
+            let evens = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            let sample: Vec<i32> = (0..20).collect();
+            assert!(one_mod_three.common_element(&three_mod_four, &sample) == Some(7));
+            assert!(three_mod_four.common_element(&evens, &sample).is_none());
+        }
+
+        #[test]
+        fn closure_under_negation() {
+            let integers = AlgaeSet::<i32>::all();
+            let naturals = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x >= 0));
+            let sample: Vec<i32> = (-5..5).collect();
+            assert!(integers.closed_under_unary(&|x| -x, &sample));
+            assert!(!naturals.closed_under_unary(&|x| -x, &sample));
+        }
+
+        #[test]
+        fn canonical_form() {
+            let mut built = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            built.or(AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x)));
+            built.and(AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == 0)));
+            let sample: Vec<i32> = (0..10).collect();
+            let canonical = built.canonical_over(&sample);
+            let explicit = AlgaeSet::from_elements(&[0, 2]);
+            assert!(canonical.pos_conditions.len() == 1);
+            assert!(canonical.neg_conditions.is_empty());
+            assert!(sample.iter().all(|x| canonical.has(x) == explicit.has(x)));
+        }
+
+        #[test]
+        fn snapshot_round_trip() {
+            let mut set = AlgaeSet::<i32>::of(|x| x % 3 == 0);
+            set.add(&7);
+            set.remove(&9);
+            let candidates: Vec<i32> = (0..12).collect();
+            let snapshot = set.to_finite_snapshot(&candidates);
+            assert!(snapshot.members == vec![0, 3, 6, 7]);
+
+            let restored = AlgaeSet::from_snapshot(&snapshot);
+            assert!(candidates.iter().all(|x| restored.has(x) == set.has(x)));
+            assert!(set.has(&12) && !restored.has(&12));
+            assert!(restored.to_finite_snapshot(&candidates) == snapshot);
+        }
+
+        #[test]
+        fn preallocated_conditions() {
+            let mut set = AlgaeSet::<i32>::with_capacity(8);
+            assert!(!set.has(&1));
+            for x in 0..4 {
+                set.add(&x);
+            }
+            assert!(set.pos_conditions.capacity() >= 8);
+            set.shrink_to_fit();
+            assert!(set.pos_conditions.capacity() == set.pos_conditions.len());
+            assert!((0..4).all(|x| set.has(&x)));
+            assert!(!set.has(&4));
+        }
+
+        #[test]
+        fn distinct_member_count() {
+            let evens = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            assert!(evens.count_members(&[2, 4, 2, 3, 4, 4, 6]) == 3);
+            assert!(evens.count_members(&[1, 3, 3]) == 0);
+        }
+
+        #[test]
+        fn member_quantifiers() {
+            let evens = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            let sample: Vec<i32> = (0..110).collect();
+            assert!(evens.for_all_members(&sample, |&x| x % 2 == 0));
+            assert!(!evens.for_all_members(&sample, |&x| x < 100));
+            assert!(evens.exists_member(&sample, |&x| x > 100));
+            assert!(!evens.exists_member(&sample, |&x| x == 101));
+        }
+
+        #[test]
+        fn boolean_algebra() {
+            let evens = || AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            let threes = || AlgaeSet::<i32>::of(|&x| x % 3 == 0);
+            let small = || {
+                let mut set = AlgaeSet::<i32>::of(|&x| x < 5);
+                set.remove(&1);
+                set.add(&9);
+                set
+            };
+            let universe: Vec<i32> = (0..12).collect();
+            let laws = AlgaeSet::boolean_algebra_laws(&evens, &threes, &small, &universe);
+            assert!(laws.len() == 4);
+            assert!(laws.iter().all(|(_, holds)| *holds));
+        }
+
+        #[test]
+        fn sorted_members() {
+            let evens = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            let sorted = evens.to_sorted_vec(&[8, 3, 2, 8, 0, 5, 2, 6]);
+            assert!(sorted == vec![0, 2, 6, 8]);
+            assert!(sorted == evens.to_sorted_vec(&[0, 2, 3, 5, 6, 8]));
+        }
+
+        #[test]
+        fn borrowed_differences() {
+            let threes = AlgaeSet::<i32>::of(|&x| x % 3 == 0);
+            let mut evens = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            evens.difference_ref(&threes);
+            let mut odds = AlgaeSet::<i32>::of(|&x| x % 2 != 0);
+            odds.or_ref(&threes);
+            let mut symmetric = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            symmetric.symmetric_difference_ref(&threes);
+            for x in 0..12 {
+                assert!(evens.has(&x) == (x % 2 == 0 && x % 3 != 0));
+                assert!(odds.has(&x) == (x % 2 != 0 || x % 3 == 0));
+                assert!(symmetric.has(&x) == ((x % 2 == 0) != (x % 3 == 0)));
+            }
+            assert!(evens.provenance().to_string() == "(set \\ set)");
+        }
+
+        #[test]
+        fn overlapping_sets_are_not_disjoint() {
+            let Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            assert!(!Z2.is_disjoint_from(&Z3, &[0, 1, 2, 3]));
+            assert!(Z2.is_disjoint_from(&Z3, &[2, 3]));
+        }
+
+        #[test]
+        fn deciding_conditions() {
+            let mut set = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            set.or(AlgaeSet::of(|&x| x > 100));
+            set.remove(&4);
+            assert!(set.has_explained(&4) == (false, 0));
+            assert!(set.has_explained(&2) == (true, 1));
+            assert!(set.has_explained(&101) == (true, 2));
+            assert!(set.has_explained(&3) == (false, 3));
+            set.complement();
+            assert!(set.has_explained(&3) == (true, 3));
+        }
+
+        #[test]
+        fn product_of_Z2() {
+            let Z2 = || AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let Z2xZ2 = AlgaeSet::product(Z2(), Z2());
+            assert!(Z2xZ2.has(&(1, 0)));
+            assert!(Z2xZ2.has(&(1, 1)));
+            assert!(!Z2xZ2.has(&(2, 0)));
+            assert!(!Z2xZ2.has(&(0, 3)));
+        }
+
+        #[test]
+        fn enumerate_Z3() {
+            let Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            assert!(Z3.elements_from(0..10) == vec![0, 1, 2]);
+            let members = Z3.elements_from(vec![2, -1, 1]);
+            assert!(PropertyType::Commutative.holds_over(&|a, b| a + b, &members));
+        }
+
+        #[test]
+        fn singletons_of_evens() {
+            let evens = AlgaeSet::<i32>::of(|&x| x % 2 == 0);
+            let sample: Vec<i32> = (0..6).collect();
+            let singletons = evens.singletons_over(&sample);
+            assert!(singletons.len() == 3);
+            for (singleton, member) in singletons.iter().zip([0, 2, 4]) {
+                assert!(singleton.elements_from(0..6) == vec![member]);
+            }
+        }
+
+        #[test]
+        fn sampled_subsets() {
+            let Z2 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 2 == x));
+            let Z3 = AlgaeSet::<i32>::mono(Box::new(|&x: &i32| x % 3 == x));
+            let sample: Vec<i32> = (-5..10).collect();
+            assert!(Z2.is_subset_of(&Z3, &sample));
+            assert!(!Z3.is_subset_of(&Z2, &sample));
+            assert!(Z3.is_subset_of(&Z2, &[0, 1, 5]));
+        }
+
+        #[test]
+        fn power_set_of_three() {
+            let universe = [1, 2, 3];
+            let lattice = AlgaeSet::<i32>::subset_lattice(&universe);
+            assert!(lattice.len() == 8);
+            assert!(universe.iter().all(|x| !lattice[0].has(x) && lattice[7].has(x)));
+            for (i, smaller) in lattice.iter().enumerate() {
+                for (j, larger) in lattice.iter().enumerate() {
+                    assert!(larger.contains_subset(smaller, &universe) == (i & j == i));
+                }
+            }
+        }
+
+        #[test]
+        fn provenance_expression() {
+            let mut evens = AlgaeSet::<i32>::all();
+            evens.and(AlgaeSet::of(|&x: &i32| x % 2 == 0).named("evens"));
+            assert!(evens.provenance().to_string() == "(all ∩ evens)");
+            evens.complement();
+            evens.or(AlgaeSet::of(|&x: &i32| x > 0));
+            assert!(evens.provenance().to_string() == "((all ∩ evens)ᶜ ∪ set)");
+        }
+
+        #[test]
+        fn unboxed_condition() {
+            let Z2 = AlgaeSet::of(|&x: &i32| x % 2 == x);
+            assert!(Z2.has(&1));
+            assert!(Z2.has(&0));
+            assert!(!Z2.has(&2));
+            assert!(!Z2.has(&-2));
+        }
+
+        #[test]
+        fn unboxed_conditions() {
+            let ends = AlgaeSet::of_many(
+                vec![-3, 3]
+                    .into_iter()
+                    .map(|b| move |&x: &i32| x == b)
+                    .collect(),
+            );
+            assert!(ends.has(&-3));
+            assert!(ends.has(&3));
+            assert!(!ends.has(&0));
+        }
+
+        #[test]
+        fn filtered_integers() {
+            let digits = AlgaeSet::<i32>::all()
+                .filter(Box::new(|&x: &i32| x > 0))
+                .filter(Box::new(|&x: &i32| x < 10));
+            assert!((-20..20).all(|x| digits.has(&x) == (1..=9).contains(&x)));
+        }
+
+        #[test]
+        fn enumerated_equality() {
+            let evens = AlgaeSet::<u8>::of(|x| x % 2 == 0);
+            let even_bits = AlgaeSet::<u8>::of(|x| x & 1 == 0);
+            let fours = AlgaeSet::<u8>::of(|x| x % 4 == 0);
+            assert!(evens == even_bits);
+            assert!(evens != fours);
+            let everything = AlgaeSet::<bool>::all();
+            assert!(everything.clone().complemented().complemented() == everything);
+        }
+
+        #[test]
+        fn debug_condition_counts() {
+            let mut set = AlgaeSet::<i32>::of(|x| x % 2 == 0);
+            set.or(AlgaeSet::of(|x| x % 3 == 0));
+            set.and(AlgaeSet::of(|&x| x > 0));
+            set.add(&-1);
+            let debug = "AlgaeSet { pos_conditions: 3, neg_conditions: 1, complemented: false }";
+            assert!(format!("{set:?}") == debug);
+            set.complement();
+            let debug = "AlgaeSet { pos_conditions: 3, neg_conditions: 1, complemented: true }";
+            assert!(format!("{set:?}") == debug);
+        }
+
+        #[test]
+        fn inclusions_leave_exclusions_in_place() {
+            let mut set = AlgaeSet::<i32>::of(|x| x % 2 == 0);
+            set.remove(&4);
+            let exclusion = Rc::clone(&set.neg_conditions[0].0);
+            for x in 0..1_000 {
+                set.add(&(2 * x + 1));
+            }
+            assert!(Rc::ptr_eq(&exclusion, &set.neg_conditions[0].0));
+            assert!(!set.has(&4) && set.has(&6) && set.has(&999));
+            set.or(AlgaeSet::of(|x| x % 4 == 0));
+            assert!(set.has(&4));
+        }
+
+        #[test]
+        fn compacted_conditions() {
+            let mut set = AlgaeSet::<i32>::of(|x| x % 2 == 0);
+            for x in 0..100 {
+                set.add(&(2 * x + 1));
+                set.remove(&(4 * x));
+            }
+            let sample: Vec<i32> = (-100..500).collect();
+            let before: Vec<bool> = sample.iter().map(|x| set.has(x)).collect();
+
+            set.compact(&sample);
+            let debug = "AlgaeSet { pos_conditions: 1, neg_conditions: 0, complemented: false }";
+            assert!(format!("{set:?}") == debug);
+            let after: Vec<bool> = sample.iter().map(|x| set.has(x)).collect();
+            assert!(before == after);
+            assert!(!set.has(&1_000_000));
+        }
+
+        #[test]
+        fn interval_endpoints() {
+            let closed = AlgaeSet::closed_interval(-1_i32, 3);
+            let open = AlgaeSet::open_interval(-1_i32, 3);
+            let half_open = AlgaeSet::half_open(-1_i32, 3);
+            assert!(closed.has(&-1) && closed.has(&3) && !closed.has(&4));
+            assert!(!open.has(&-1) && open.has(&0) && !open.has(&3));
+            assert!(half_open.has(&-1) && half_open.has(&2) && !half_open.has(&3));
+
+            let closed = AlgaeSet::closed_interval(0_f32, 1_f32);
+            let open = AlgaeSet::open_interval(0_f32, 1_f32);
+            let half_open = AlgaeSet::half_open(0_f32, 1_f32);
+            assert!(closed.has(&0_f32) && closed.has(&1_f32) && !closed.has(&1.01_f32));
+            assert!(!open.has(&0_f32) && open.has(&0.5_f32) && !open.has(&1_f32));
+            assert!(half_open.has(&0_f32) && !half_open.has(&1_f32));
+            assert!(!closed.has(&f32::NAN));
+        }
+
+        #[test]
+        fn convex_sets() {
+            let sample: Vec<i32> = (-10..10).collect();
+            assert!(AlgaeSet::closed_interval(-3, 4).is_convex_over(&sample));
+            assert!(!AlgaeSet::<i32>::of(|x| x % 2 == 0).is_convex_over(&sample));
+        }
+
+        #[test]
+        fn collected_range() {
+            let set: AlgaeSet<i32> = (0..5).collect();
+            assert!((0..5).all(|x| set.has(&x)));
+            assert!(!set.has(&-1));
+            assert!(!set.has(&5));
+        }
+
+        #[test]
+        fn family_intersection() {
+            let family = [
+                AlgaeSet::<i32>::of(|x| x % 2 == 0),
+                AlgaeSet::<i32>::of(|x| x % 3 == 0),
+                AlgaeSet::<i32>::of(|x| x % 5 == 0),
+            ];
+            let sample: Vec<i32> = (0..100).collect();
+            assert!(AlgaeSet::intersect_over(&family, &sample) == vec![0, 30, 60, 90]);
+            assert!(AlgaeSet::intersect_over(&family[..0], &sample) == sample);
+        }
+
+        #[test]
+        fn separated_pairs() {
+            let evens = AlgaeSet::<i32>::of(|x| x % 2 == 0);
+            assert!(evens.separates(&2, &3));
+            assert!(evens.separates(&3, &2));
+            assert!(!evens.separates(&2, &4));
+            assert!(!evens.separates(&3, &5));
+        }
+
+        #[test]
+        fn pulled_back_membership() {
+            #[derive(Clone, Copy)]
+            enum Digit {
+                Zero,
+                One,
+                Two,
+                Three,
+            }
+            let value = |d: &Digit| match d {
+                Digit::Zero => 0,
+                Digit::One => 1,
+                Digit::Two => 2,
+                Digit::Three => 3,
+            };
+            let Z3 = AlgaeSet::<i32>::of(|&x| x % 3 == x);
+            let digits = Z3.clone().contravariant_map(Box::new(value));
+            for d in [Digit::Zero, Digit::One, Digit::Two, Digit::Three] {
+                assert!(digits.has(&d) == Z3.has(&value(&d)));
+            }
+            assert!(digits.has(&Digit::Two));
+            assert!(!digits.has(&Digit::Three));
         }
     }
 }
@@ -0,0 +1,231 @@
+pub use crate::mapping::Counterexample;
+use crate::mapping::PropertyError;
+
+/// The structures [`random_classify`] can recognize, from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Structure {
+    /// The operation is neither associative nor cancellative, and has no identity
+    Magma,
+    /// An identity exists, but the operation is neither associative nor cancellative
+    UnitalMagma,
+    /// The operation is cancellative, but has no identity
+    Quasigroup,
+    /// The operation is associative, but has no identity
+    Groupoid,
+    /// The operation is cancellative with an identity, but isn't associative
+    Loop,
+    /// The operation is associative with an identity, but some element has no inverse
+    Monoid,
+    /// The operation is associative with an identity and inverses, but isn't commutative
+    Group,
+    /// Every group law holds, and the operation is commutative too
+    AbelianGroup,
+}
+
+/// The result of a [`random_classify`] run.
+#[derive(Debug)]
+pub struct Classification<T> {
+    /// The strongest structure whose laws all hold over `sample`
+    pub structure: Structure,
+    /// The element of `sample` acting as a two-sided identity on it, if any
+    pub identity: Option<T>,
+    /// The distinct elements drawn, in the order they were first drawn
+    pub sample: Vec<T>,
+    /// A witness for each law that fails over `sample`
+    pub counterexamples: Vec<Counterexample<T>>,
+}
+
+/// Classifies `op` over `trials` elements drawn from `sampler`
+///
+/// The drawn elements are accumulated into a sample, over which each law is
+/// checked. The strongest [`Structure`] whose laws all hold over the sample
+/// is reported, and every law that fails is flagged with a counterexample.
+/// Inverses are searched for within the sample, so groups are only
+/// recognized once the sample contains every inverse.
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::classify::{random_classify, Structure};
+///
+/// let mut next = 0;
+/// let sampler = || {
+///     next = (next + 1) % 5;
+///     next
+/// };
+/// let classification = random_classify(&|a, b| (a + b) % 5, sampler, 20);
+/// assert!(classification.structure == Structure::AbelianGroup);
+/// assert!(classification.identity == Some(0));
+/// ```
+pub fn random_classify<T: Copy + PartialEq>(
+    op: &dyn Fn(T, T) -> T,
+    mut sampler: impl FnMut() -> T,
+    trials: usize,
+) -> Classification<T> {
+    let mut sample = vec![];
+    for _ in 0..trials {
+        let element = sampler();
+        if !sample.contains(&element) {
+            sample.push(element);
+        }
+    }
+
+    let mut counterexamples = vec![];
+    let associative = find_associativity_violation(op, &sample);
+    let commutative = find_commutativity_violation(op, &sample);
+    let cancellative = find_cancellativity_violation(op, &sample);
+    let identity = sample.iter().copied().find(|e| {
+        sample
+            .iter()
+            .all(|a| (op)(*e, *a) == *a && (op)(*a, *e) == *a)
+    });
+    let uninvertible = identity.and_then(|e| {
+        sample
+            .iter()
+            .copied()
+            .find(|a| !sample.iter().any(|b| (op)(*a, *b) == e && (op)(*b, *a) == e))
+    });
+
+    for (law, violation) in [
+        (PropertyError::AssociativityError, &associative),
+        (PropertyError::CommutativityError, &commutative),
+        (PropertyError::CancellativityError, &cancellative),
+    ] {
+        if let Some(elements) = violation {
+            counterexamples.push(Counterexample {
+                law,
+                elements: elements.clone(),
+            });
+        }
+    }
+    if identity.is_none() {
+        counterexamples.push(Counterexample {
+            law: PropertyError::IdentityError,
+            elements: vec![],
+        });
+    }
+    if let Some(element) = uninvertible {
+        counterexamples.push(Counterexample {
+            law: PropertyError::InvertibilityError,
+            elements: vec![element],
+        });
+    }
+
+    let associative = associative.is_none();
+    let cancellative = cancellative.is_none();
+    let structure = match identity {
+        Some(_) if associative && uninvertible.is_none() => {
+            if commutative.is_none() {
+                Structure::AbelianGroup
+            } else {
+                Structure::Group
+            }
+        }
+        Some(_) if associative => Structure::Monoid,
+        Some(_) if cancellative => Structure::Loop,
+        None if associative => Structure::Groupoid,
+        None if cancellative => Structure::Quasigroup,
+        Some(_) => Structure::UnitalMagma,
+        None => Structure::Magma,
+    };
+
+    Classification {
+        structure,
+        identity,
+        sample,
+        counterexamples,
+    }
+}
+
+fn find_associativity_violation<T: Copy + PartialEq>(
+    op: &dyn Fn(T, T) -> T,
+    sample: &[T],
+) -> Option<Vec<T>> {
+    for a in sample {
+        for b in sample {
+            for c in sample {
+                if (op)((op)(*a, *b), *c) != (op)(*a, (op)(*b, *c)) {
+                    return Some(vec![*a, *b, *c]);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn find_commutativity_violation<T: Copy + PartialEq>(
+    op: &dyn Fn(T, T) -> T,
+    sample: &[T],
+) -> Option<Vec<T>> {
+    for a in sample {
+        for b in sample {
+            if (op)(*a, *b) != (op)(*b, *a) {
+                return Some(vec![*a, *b]);
+            }
+        }
+    }
+    None
+}
+
+fn find_cancellativity_violation<T: Copy + PartialEq>(
+    op: &dyn Fn(T, T) -> T,
+    sample: &[T],
+) -> Option<Vec<T>> {
+    for a in sample {
+        for b in sample {
+            for c in sample {
+                if b == c {
+                    continue;
+                }
+                if (op)(*a, *b) == (op)(*a, *c) || (op)(*b, *a) == (op)(*c, *a) {
+                    return Some(vec![*a, *b, *c]);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn pseudorandom_bytes(seed: u32, mask: u8) -> impl FnMut() -> u8 {
+        let mut state = seed;
+        move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 16) as u8 & mask
+        }
+    }
+
+    #[test]
+    fn xor_is_abelian_group() {
+        let classification = random_classify(&|a, b| a ^ b, pseudorandom_bytes(7, 0b111), 64);
+        assert!(classification.sample.len() == 8);
+        assert!(classification.structure == Structure::AbelianGroup);
+        assert!(classification.identity == Some(0));
+        assert!(classification.counterexamples.is_empty());
+    }
+
+    #[test]
+    fn subtraction_has_counterexamples() {
+        let sub = |a: i8, b: i8| a.wrapping_sub(b);
+        let mut next = -3;
+        let sampler = || {
+            next += 1;
+            next
+        };
+        let classification = random_classify(&sub, sampler, 6);
+        assert!(classification.structure == Structure::Quasigroup);
+        let associativity = classification
+            .counterexamples
+            .iter()
+            .find(|c| matches!(c.law, PropertyError::AssociativityError))
+            .unwrap();
+        let [a, b, c] = associativity.elements[..] else {
+            panic!()
+        };
+        assert!(sub(sub(a, b), c) != sub(a, sub(b, c)));
+    }
+}
@@ -1,7 +1,12 @@
 
 use crate::algaeset::AlgaeSet;
-use crate::mapping::{PropertyType, BinaryOperation, binop_has_invertible_identity, binop_is_invertible};
-use crate::magma::{Magmoid, Magma, UnitalMagma, Quasigroup};
+use crate::mapping::{
+    PropertyType, BinaryOperation, Counterexample, binop_has_invertible_identity,
+    binop_is_invertible, invertible_parts, SemidirectProductOperation,
+};
+use crate::magma::{
+    opposite_conversions, precondition_setters, Carrier, Magmoid, Magma, UnitalMagma, Quasigroup,
+};
 
 /// A monoid with inverses.
 ///
@@ -42,6 +47,7 @@ pub struct Group<'a, T> {
     aset: AlgaeSet<T>,
     binop: &'a mut dyn BinaryOperation<T>,
     identity: T,
+    precondition: Option<&'a dyn Fn(T, T) -> bool>,
 }
 
 impl<'a, T: Copy + PartialEq> Group<'a, T> {
@@ -54,30 +60,749 @@ impl<'a, T: Copy + PartialEq> Group<'a, T> {
             aset,
             binop,
             identity,
+            precondition: None,
         }
     }
+
+    /// Returns the number of orbits of `set_elements` under `action`
+    ///
+    /// By Burnside's lemma this is the average number of points of
+    /// `set_elements` fixed by each of `group_elements`, which must list the
+    /// whole group. An empty `group_elements` lists no group, so it has no
+    /// orbit count.
+    pub fn count_orbits<X: Copy + PartialEq>(
+        &self,
+        action: &dyn Fn(T, X) -> X,
+        group_elements: &[T],
+        set_elements: &[X],
+    ) -> Option<usize> {
+        if group_elements.is_empty() {
+            return None;
+        }
+        let fixed_points: usize = group_elements
+            .iter()
+            .map(|g| {
+                set_elements
+                    .iter()
+                    .filter(|x| (action)(*g, **x) == **x)
+                    .count()
+            })
+            .sum();
+        // Burnside's lemma makes the total a multiple of the group order,
+        // unless `group_elements` isn't the whole group or `action` isn't one
+        debug_assert_eq!(fixed_points % group_elements.len(), 0);
+        Some(fixed_points / group_elements.len())
+    }
+
+    /// Returns the conjugate `g·x·g⁻¹` of `x` by `g`
+    pub fn conjugate(&mut self, g: T, x: T) -> Result<T, Counterexample<T>> {
+        let (identity, inv) = invertible_parts(self.binop);
+        let g_inverse = (inv)(identity, g);
+        let gx = self.with(g, x)?;
+        self.with(gx, g_inverse)
+    }
+
+    /// Returns the derived subgroup of the finite group listed by `elements`
+    ///
+    /// The derived subgroup is generated by every commutator `a·b·a⁻¹·b⁻¹`.
+    /// If the commutators generate more elements than `elements` lists, the
+    /// listing isn't a group and there is no derived subgroup.
+    pub fn derived_subgroup(&self, elements: &[T]) -> Option<Vec<T>> {
+        let op = self.binop.operation();
+        let (identity, inv) = invertible_parts(self.binop);
+        let mut commutators = vec![];
+        for a in elements {
+            for b in elements {
+                let commutator = (op)((op)((op)(*a, *b), (inv)(identity, *a)), (inv)(identity, *b));
+                if !commutators.contains(&commutator) {
+                    commutators.push(commutator);
+                }
+            }
+        }
+        self.generated_subgroup(&commutators, elements.len())
+    }
+
+    /// Returns whether the finite group listed by `elements` is its own derived subgroup
+    pub fn is_perfect(&self, elements: &[T]) -> Option<bool> {
+        Some(self.derived_subgroup(elements)?.len() == elements.len())
+    }
+
+    /// Returns the cosets of the derived subgroup of the finite group listed by `elements`
+    ///
+    /// These cosets are the elements of the abelianization `G/[G,G]`, the
+    /// largest abelian quotient of the group. Cosets multiply through any of
+    /// their members, and the coset containing the identity is `[G,G]` itself.
+    pub fn abelianization(&self, elements: &[T]) -> Option<Vec<Vec<T>>> {
+        let op = self.binop.operation();
+        let derived = self.derived_subgroup(elements)?;
+        let cosets = self
+            .transversal(&derived, elements)
+            .into_iter()
+            .map(|g| derived.iter().map(|h| (op)(g, *h)).collect())
+            .collect();
+        Some(cosets)
+    }
+
+    /// Returns whether the derived series of the finite group listed by
+    /// `elements` reaches the trivial subgroup
+    ///
+    /// The series `G ⊇ [G,G] ⊇ [[G,G],[G,G]] ⊇ ⋯` either shrinks to the
+    /// trivial subgroup or stalls at a nontrivial perfect subgroup. A listing
+    /// that isn't a group has no derived series, giving `None`.
+    pub fn is_solvable(&self, elements: &[T]) -> Option<bool> {
+        let mut current = elements.to_vec();
+        while current.len() > 1 {
+            let derived = self.derived_subgroup(&current)?;
+            if derived.len() == current.len() {
+                return Some(false);
+            }
+            current = derived;
+        }
+        Some(true)
+    }
+
+    /// Returns the invariant factors `d₁ | d₂ | ⋯` of the finite abelian group
+    /// listed by `elements`
+    ///
+    /// By the structure theorem the group is isomorphic to `ℤ/d₁ × ℤ/d₂ × ⋯`.
+    /// For each prime `p`, the number of elements whose orders divide `pᵏ`
+    /// fixes how many cyclic `p`-power factors have order at least `pᵏ`.
+    #[allow(clippy::manual_is_multiple_of, clippy::manual_repeat_n)]
+    pub fn invariant_factors(&self, elements: &[T]) -> Vec<u32> {
+        let order = elements.len() as u32;
+        let orders: Vec<u32> = elements
+            .iter()
+            .filter_map(|x| self.order_of_element(*x, order))
+            .collect();
+        let mut prime_powers: Vec<Vec<u32>> = vec![];
+        let mut remaining = order;
+        let mut p = 2;
+        while remaining > 1 {
+            if remaining % p != 0 {
+                p += 1;
+                continue;
+            }
+            while remaining % p == 0 {
+                remaining /= p;
+            }
+            // factors_at_least[k] counts the cyclic factors of order at least pᵏ⁺¹
+            let mut factors_at_least = vec![];
+            let mut rank = 0;
+            let mut power = p;
+            loop {
+                let mut count = orders.iter().filter(|o| power % **o == 0).count() as u32;
+                let mut log = 0;
+                while count > 1 {
+                    count /= p;
+                    log += 1;
+                }
+                if log == rank {
+                    break;
+                }
+                factors_at_least.push(log - rank);
+                rank = log;
+                power *= p;
+            }
+            let mut powers = vec![];
+            for (k, count) in factors_at_least.iter().enumerate() {
+                let exactly = count - factors_at_least.get(k + 1).unwrap_or(&0);
+                powers.extend(std::iter::repeat(p.pow(k as u32 + 1)).take(exactly as usize));
+            }
+            powers.sort_by(|a, b| b.cmp(a));
+            prime_powers.push(powers);
+        }
+        let length = prime_powers.iter().map(|powers| powers.len()).max().unwrap_or(0);
+        let mut factors: Vec<u32> = (0..length)
+            .map(|i| prime_powers.iter().filter_map(|powers| powers.get(i)).product())
+            .collect();
+        factors.reverse();
+        factors
+    }
+
+    /// Returns one representative of each left coset of `subgroup` in `elements`
+    ///
+    /// Representatives are the first element of `elements` found in each
+    /// coset, so the identity represents `subgroup` itself.
+    pub fn transversal(&self, subgroup: &[T], elements: &[T]) -> Vec<T> {
+        let op = self.binop.operation();
+        let mut representatives = vec![];
+        let mut covered = vec![];
+        for g in elements {
+            if covered.contains(g) {
+                continue;
+            }
+            representatives.push(*g);
+            covered.extend(subgroup.iter().map(|h| (op)(*g, *h)));
+        }
+        representatives
+    }
+
+    /// Returns the Schreier generators of `subgroup`, given `generators` of
+    /// the whole group and a left `transversal` of `subgroup`
+    ///
+    /// For each representative `t` and generator `s`, `r⁻¹·s·t` lies in
+    /// `subgroup`, where `r` represents the coset of `s·t`. Together these
+    /// generate `subgroup`; duplicates and the identity are left out.
+    pub fn schreier_generators(
+        &self,
+        subgroup: &[T],
+        generators: &[T],
+        transversal: &[T],
+    ) -> Vec<T> {
+        let op = self.binop.operation();
+        let (identity, inv) = invertible_parts(self.binop);
+        let mut schreier = vec![];
+        for t in transversal {
+            for s in generators {
+                let st = (op)(*s, *t);
+                let Some(generator) = transversal
+                    .iter()
+                    .map(|r| (op)((inv)(identity, *r), st))
+                    .find(|g| subgroup.contains(g))
+                else {
+                    continue;
+                };
+                if generator != identity && !schreier.contains(&generator) {
+                    schreier.push(generator);
+                }
+            }
+        }
+        schreier
+    }
+
+    /// Returns every subgroup of the finite group listed by `elements`
+    ///
+    /// Subgroups are found by closing each known subgroup under one more
+    /// generator, starting from the trivial subgroup. Closures are cut off
+    /// once they outgrow the group, and those whose orders don't divide the
+    /// group order are discarded by Lagrange's theorem.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `elements` lists more than 64 elements, since the number of
+    /// subgroups can grow exponentially with the group order: `(ℤ/2ℤ)⁶`
+    /// alone has 2825 of them.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn subgroups(&self, elements: &[T]) -> Vec<Vec<T>> {
+        let order = elements.len();
+        assert!(order <= 64);
+        let mut subgroups = vec![vec![self.identity]];
+        let mut unexpanded = 0;
+        while unexpanded < subgroups.len() {
+            let subgroup = subgroups[unexpanded].clone();
+            unexpanded += 1;
+            for g in elements {
+                if subgroup.contains(g) {
+                    continue;
+                }
+                let mut generators = subgroup.clone();
+                generators.push(*g);
+                let Some(generated) = self.generated_subgroup(&generators, order) else {
+                    continue;
+                };
+                if order % generated.len() != 0 {
+                    continue;
+                }
+                let known = subgroups.iter().any(|h| {
+                    h.len() == generated.len() && generated.iter().all(|x| h.contains(x))
+                });
+                if !known {
+                    subgroups.push(generated);
+                }
+            }
+        }
+        subgroups
+    }
+
+    /// Returns the closure of `generators` under the group operation, unless
+    /// it grows past `bound` elements
+    fn generated_subgroup(&self, generators: &[T], bound: usize) -> Option<Vec<T>> {
+        let op = self.binop.operation();
+        let mut generated = vec![self.identity];
+        let mut unexpanded = 0;
+        while unexpanded < generated.len() {
+            let x = generated[unexpanded];
+            unexpanded += 1;
+            for g in generators {
+                let product = (op)(x, *g);
+                if !generated.contains(&product) {
+                    if generated.len() == bound {
+                        return None;
+                    }
+                    generated.push(product);
+                }
+            }
+        }
+        Some(generated)
+    }
+
+    /// Returns the inverse of `x` found by searching `elements`
+    ///
+    /// This works from the group's multiplication alone, so it doesn't need
+    /// an inverse function. Inverses are stored in the operation's inverse
+    /// cache, if it has one, so repeated queries for the same element don't
+    /// search `elements` again.
+    pub fn inverse(&mut self, x: T, elements: &[T]) -> Option<T> {
+        if let Some(inverse) = self.binop.cached_inverse(x) {
+            return Some(inverse);
+        }
+        let op = self.binop.operation();
+        let inverse = elements
+            .iter()
+            .find(|y| (op)(x, **y) == self.identity && (op)(**y, x) == self.identity)
+            .copied()?;
+        self.binop.cache_inverse(x, inverse);
+        self.binop.cache_inverse(inverse, x);
+        Some(inverse)
+    }
+
+    /// Returns the order of `x` if it is at most `bound`
+    pub fn order_of_element(&self, x: T, bound: u32) -> Option<u32> {
+        let op = self.binop.operation();
+        let mut power = x;
+        for order in 1..=bound {
+            if power == self.identity {
+                return Some(order);
+            }
+            power = (op)(power, x);
+        }
+        None
+    }
+
+    /// Returns whether every element of `sample` has order at most `bound`
+    ///
+    /// A `false` result means some element has order past `bound`, if not
+    /// infinite order, so algorithms assuming a finite group shouldn't be
+    /// trusted over `sample`.
+    pub fn appears_finite_order(&self, sample: &[T], bound: u32) -> bool {
+        sample
+            .iter()
+            .all(|x| self.order_of_element(*x, bound).is_some())
+    }
+
+    /// Returns the elements of `sample` whose orders are at most `bound`
+    pub fn torsion_elements(&self, sample: &[T], bound: u32) -> Vec<T> {
+        sample
+            .iter()
+            .filter(|x| self.order_of_element(**x, bound).is_some())
+            .copied()
+            .collect()
+    }
+}
+
+impl<'a, N: Copy + PartialEq + 'static, H: Copy + PartialEq + 'static> Group<'a, (N, H)> {
+    /// Returns the semidirect product `N ⋊ H` of `normal` and `acting`
+    ///
+    /// Pairs multiply by the twisted rule `(n₁, h₁)·(n₂, h₂) = (n₁·φ(h₁, n₂), h₁·h₂)`,
+    /// where `φ` is `action`, which must act on `N` by automorphisms. Groups
+    /// only borrow their operations, so the [`SemidirectProductOperation`] is
+    /// kept in `product`.
+    pub fn semidirect_product<'g>(
+        normal: &'g Group<'_, N>,
+        acting: &'g Group<'_, H>,
+        action: &'g dyn Fn(H, N) -> N,
+        product: &'a mut Option<SemidirectProductOperation<'g, N, H>>,
+    ) -> Self
+    where
+        'g: 'a,
+    {
+        let aset = AlgaeSet::product(normal.aset.clone(), acting.aset.clone());
+        let identity = (normal.identity, acting.identity);
+        let binop = product.insert(SemidirectProductOperation::new(
+            &*normal.binop,
+            &*acting.binop,
+            action,
+        ));
+        Group::new(aset, binop, identity)
+    }
 }
 
+precondition_setters!(Group);
+
+opposite_conversions!(Group(identity));
+
 impl<'a, T: Copy + PartialEq> Magmoid<T> for Group<'a, T> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T> {
         self.binop
     }
+
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        self.precondition
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Carrier<T> for Group<'a, T> {
+    fn aset(&self) -> &AlgaeSet<T> {
+        &self.aset
+    }
 }
 
 impl<'a, T> From<Group<'a, T>> for Magma<'a, T> {
     fn from(group: Group<'a, T>) -> Magma<'a, T> {
-        Magma::new(group.aset, group.binop)
+        Magma::new(group.aset, group.binop).preconditioned(group.precondition)
     }
 }
 
 impl<'a, T: Copy + PartialEq> From<Group<'a, T>> for UnitalMagma<'a, T> {
     fn from(group: Group<'a, T>) -> UnitalMagma<'a, T> {
-        UnitalMagma::new(group.aset, group.binop, group.identity)
+        UnitalMagma::new(group.aset, group.binop, group.identity).preconditioned(group.precondition)
     }
 }
 
 impl<'a, T: Copy + PartialEq> From<Group<'a, T>> for Quasigroup<'a, T> {
     fn from(group: Group<'a, T>) -> Quasigroup<'a, T> {
-        Quasigroup::new(group.aset, group.binop)
+        Quasigroup::new(group.aset, group.binop).preconditioned(group.precondition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::mapping::GroupOperation;
+
+    type Permutation = [usize; 3];
+
+    const D3: [Permutation; 6] = [
+        [0, 1, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [0, 2, 1],
+        [2, 1, 0],
+        [1, 0, 2],
+    ];
+
+    fn compose(a: Permutation, b: Permutation) -> Permutation {
+        [a[b[0]], a[b[1]], a[b[2]]]
+    }
+
+    fn invert(a: Permutation) -> Permutation {
+        let mut inverse = [0; 3];
+        for (i, image) in a.iter().enumerate() {
+            inverse[*image] = i;
+        }
+        inverse
     }
-}
\ No newline at end of file
+
+    fn divide(a: Permutation, b: Permutation) -> Permutation {
+        compose(a, invert(b))
+    }
+
+    #[test]
+    fn dihedral_group() {
+        let mut dihedral = GroupOperation::new(&compose, &divide, D3[0]);
+        let mut group = Group::new(AlgaeSet::<Permutation>::all(), &mut dihedral, D3[0]);
+        for a in D3 {
+            for b in D3 {
+                let product = group.with(a, b);
+                assert!(product.is_ok());
+                assert!(product.unwrap() == compose(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn nonabelian_right_division() {
+        // Right division inv(a, b) = a·b⁻¹ gives inv(a, b)·b == a even though a·b ≠ b·a
+        let invertible = PropertyType::Invertible(D3[0], &divide);
+        assert!(invertible.holds_over(&compose, &D3));
+        let left_division = |a: Permutation, b: Permutation| compose(invert(b), a);
+        let misdivided = PropertyType::Invertible(D3[0], &left_division);
+        assert!(!misdivided.holds_over(&compose, &D3));
+    }
+
+    #[test]
+    fn opposite_is_isomorphic_via_inversion() {
+        let mut dihedral = GroupOperation::new(&compose, &divide, D3[0]);
+        let d3 = Group::new(AlgaeSet::<Permutation>::all(), &mut dihedral, D3[0]);
+        let mut opposite = None;
+        let mut opposite_group = d3.into_opposite(&mut opposite);
+        let mut differs_from_original = false;
+        for a in D3 {
+            for b in D3 {
+                let product = opposite_group.with(invert(a), invert(b));
+                assert!(product.is_ok());
+                assert!(product.unwrap() == invert(compose(a, b)));
+                if opposite_group.with(a, b).unwrap() != compose(a, b) {
+                    differs_from_original = true;
+                }
+            }
+        }
+        assert!(differs_from_original);
+    }
+
+    #[test]
+    fn torsion_of_integers_times_z3() {
+        let add = |a: (i32, i32), b: (i32, i32)| (a.0 + b.0, (a.1 + b.1) % 3);
+        let sub = |a: (i32, i32), b: (i32, i32)| (a.0 - b.0, (a.1 - b.1).rem_euclid(3));
+        let mut op = GroupOperation::new(&add, &sub, (0, 0));
+        let group = Group::new(AlgaeSet::<(i32, i32)>::all(), &mut op, (0, 0));
+        let sample: Vec<(i32, i32)> = (-2..=2).flat_map(|a| (0..3).map(move |b| (a, b))).collect();
+        assert!(group.order_of_element((0, 1), 10) == Some(3));
+        assert!(group.order_of_element((1, 0), 10).is_none());
+        assert!(group.torsion_elements(&sample, 10) == vec![(0, 0), (0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn finite_and_infinite_orders() {
+        let add = |a: u8, b: u8| (a + b) % 5;
+        let sub = |a: u8, b: u8| (a + 5 - b) % 5;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z5 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        assert!(z5.appears_finite_order(&[0, 1, 2, 3, 4], 5));
+        assert!(!z5.appears_finite_order(&[0, 1, 2, 3, 4], 4));
+
+        let mut op = GroupOperation::new(&|a: i32, b: i32| a + b, &|a, b| a - b, 0);
+        let integers = Group::new(AlgaeSet::<i32>::all(), &mut op, 0);
+        assert!(integers.appears_finite_order(&[0], 100));
+        assert!(!integers.appears_finite_order(&[0, 1, -2], 100));
+    }
+
+    #[test]
+    fn inverse_from_table() {
+        const TABLE: [[usize; 4]; 4] = [[0, 1, 2, 3], [1, 2, 3, 0], [2, 3, 0, 1], [3, 0, 1, 2]];
+        let products = Cell::new(0);
+        let mul = |a: usize, b: usize| {
+            products.set(products.get() + 1);
+            TABLE[a][b]
+        };
+        // The table is all there is, so inverses can only come from searching it
+        let no_inverse = |_: usize, _: usize| -> usize { unreachable!() };
+        let mut op = GroupOperation::new(&mul, &no_inverse, 0).with_inverse_cache();
+        let mut z4 = Group::new(AlgaeSet::<usize>::all(), &mut op, 0);
+        let elements = [0, 1, 2, 3];
+        assert!(z4.inverse(1, &elements) == Some(3));
+        assert!(z4.inverse(2, &elements) == Some(2));
+        assert!(z4.inverse(0, &[1, 2, 3]).is_none());
+
+        let searched = products.get();
+        assert!(z4.inverse(1, &elements) == Some(3));
+        assert!(z4.inverse(3, &[]) == Some(1));
+        assert!(products.get() == searched);
+    }
+
+    #[test]
+    fn conjugation() {
+        let add = |a: u8, b: u8| (a + b) % 5;
+        let sub = |a: u8, b: u8| (a + 5 - b) % 5;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let mut z5 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        for g in 0..5 {
+            assert!(z5.conjugate(g, 3).unwrap() == 3);
+        }
+
+        let mut dihedral = GroupOperation::new(&compose, &divide, D3[0]);
+        let mut d3 = Group::new(AlgaeSet::<Permutation>::all(), &mut dihedral, D3[0]);
+        let conjugate = d3.conjugate(D3[1], D3[3]).unwrap();
+        assert!(conjugate == compose(compose(D3[1], D3[3]), invert(D3[1])));
+        assert!(conjugate != D3[3]);
+    }
+
+    #[test]
+    fn transversal_of_order_two_subgroup() {
+        let add = |a: u8, b: u8| (a + b) % 6;
+        let sub = |a: u8, b: u8| (a + 6 - b) % 6;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z6 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        let transversal = z6.transversal(&[0, 3], &[0, 1, 2, 3, 4, 5]);
+        assert!(transversal == vec![0, 1, 2]);
+        let mut residues: Vec<u8> = transversal.iter().map(|g| g % 3).collect();
+        residues.sort();
+        assert!(residues == vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn invariant_factors() {
+        let add = |a: u8, b: u8| (a + b) % 4;
+        let sub = |a: u8, b: u8| (a + 4 - b) % 4;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z4 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        assert!(z4.invariant_factors(&[0, 1, 2, 3]) == vec![4]);
+
+        let mut xor = GroupOperation::new(&|a: u8, b: u8| a ^ b, &|a, b| a ^ b, 0);
+        let klein = Group::new(AlgaeSet::<u8>::all(), &mut xor, 0);
+        assert!(klein.invariant_factors(&[0, 1, 2, 3]) == vec![2, 2]);
+
+        let add = |a: (u8, u8), b: (u8, u8)| ((a.0 + b.0) % 2, (a.1 + b.1) % 6);
+        let sub = |a: (u8, u8), b: (u8, u8)| ((a.0 + b.0) % 2, (a.1 + 6 - b.1) % 6);
+        let mut op = GroupOperation::new(&add, &sub, (0, 0));
+        let z2_z6 = Group::new(AlgaeSet::<(u8, u8)>::all(), &mut op, (0, 0));
+        let elements: Vec<(u8, u8)> = (0..2).flat_map(|a| (0..6).map(move |b| (a, b))).collect();
+        assert!(z2_z6.invariant_factors(&elements) == vec![2, 6]);
+    }
+
+    #[test]
+    fn derived_subgroups() {
+        let add = |a: u8, b: u8| (a + b) % 6;
+        let sub = |a: u8, b: u8| (a + 6 - b) % 6;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z6 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        assert!(z6.derived_subgroup(&[0, 1, 2, 3, 4, 5]) == Some(vec![0]));
+        assert!(z6.is_perfect(&[0, 1, 2, 3, 4, 5]) == Some(false));
+
+        let mut dihedral = GroupOperation::new(&compose, &divide, D3[0]);
+        let d3 = Group::new(AlgaeSet::<Permutation>::all(), &mut dihedral, D3[0]);
+        let derived = d3.derived_subgroup(&D3).unwrap();
+        assert!(derived.len() == 3);
+        assert!(D3[..3].iter().all(|rotation| derived.contains(rotation)));
+    }
+
+    #[test]
+    fn abelianizations() {
+        let add = |a: u8, b: u8| (a + b) % 6;
+        let sub = |a: u8, b: u8| (a + 6 - b) % 6;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z6 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        let cosets = z6.abelianization(&[0, 1, 2, 3, 4, 5]).unwrap();
+        assert!(cosets == vec![vec![0], vec![1], vec![2], vec![3], vec![4], vec![5]]);
+
+        let mut dihedral = GroupOperation::new(&compose, &divide, D3[0]);
+        let d3 = Group::new(AlgaeSet::<Permutation>::all(), &mut dihedral, D3[0]);
+        let cosets = d3.abelianization(&D3).unwrap();
+        assert!(cosets.len() == 2);
+        let rotations = cosets.iter().find(|c| c.contains(&D3[0])).unwrap();
+        let reflections = cosets.iter().find(|c| !c.contains(&D3[0])).unwrap();
+        assert!(D3[..3].iter().all(|r| rotations.contains(r)));
+        assert!(D3[3..].iter().all(|r| reflections.contains(r)));
+        // Two reflections compose to a rotation, so the quotient is ℤ/2ℤ
+        assert!(rotations.contains(&compose(reflections[0], reflections[1])));
+    }
+
+    type Permutation5 = [usize; 5];
+
+    fn compose5(a: Permutation5, b: Permutation5) -> Permutation5 {
+        b.map(|i| a[i])
+    }
+
+    fn divide5(a: Permutation5, b: Permutation5) -> Permutation5 {
+        let mut inverse = [0; 5];
+        for (i, image) in b.iter().enumerate() {
+            inverse[*image] = i;
+        }
+        compose5(a, inverse)
+    }
+
+    fn alternating_group_a5() -> Vec<Permutation5> {
+        let is_even = |p: &Permutation5| {
+            let inversions = (0..5)
+                .flat_map(|i| (i + 1..5).map(move |j| (i, j)))
+                .filter(|(i, j)| p[*i] > p[*j])
+                .count();
+            inversions % 2 == 0
+        };
+        let mut a5: Vec<Permutation5> = vec![];
+        for code in 0..5usize.pow(5) {
+            let p = [code % 5, code / 5 % 5, code / 25 % 5, code / 125 % 5, code / 625];
+            let bijective = (0..5).all(|i| p.contains(&i));
+            if bijective && is_even(&p) {
+                a5.push(p);
+            }
+        }
+        a5
+    }
+
+    #[test]
+    fn alternating_group_is_perfect() {
+        let a5 = alternating_group_a5();
+        assert!(a5.len() == 60);
+        let identity = [0, 1, 2, 3, 4];
+        let mut op = GroupOperation::new(&compose5, &divide5, identity);
+        let group = Group::new(AlgaeSet::<Permutation5>::all(), &mut op, identity);
+        assert!(group.is_perfect(&a5) == Some(true));
+    }
+
+    #[test]
+    fn solvable_groups() {
+        let add = |a: u8, b: u8| (a + b) % 6;
+        let sub = |a: u8, b: u8| (a + 6 - b) % 6;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z6 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        assert!(z6.is_solvable(&[0, 1, 2, 3, 4, 5]) == Some(true));
+
+        let mut dihedral = GroupOperation::new(&compose, &divide, D3[0]);
+        let d3 = Group::new(AlgaeSet::<Permutation>::all(), &mut dihedral, D3[0]);
+        assert!(d3.is_solvable(&D3) == Some(true));
+        assert!(d3.is_solvable(&[D3[1], D3[3]]).is_none());
+
+        let identity = [0, 1, 2, 3, 4];
+        let mut op = GroupOperation::new(&compose5, &divide5, identity);
+        let a5 = Group::new(AlgaeSet::<Permutation5>::all(), &mut op, identity);
+        assert!(a5.is_solvable(&alternating_group_a5()) == Some(false));
+    }
+
+    #[test]
+    fn schreier_generators_of_even_residues() {
+        let add = |a: u8, b: u8| (a + b) % 6;
+        let sub = |a: u8, b: u8| (a + 6 - b) % 6;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z6 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        let evens = [0, 2, 4];
+        let transversal = z6.transversal(&evens, &[0, 1, 2, 3, 4, 5]);
+        let generators = z6.schreier_generators(&evens, &[1], &transversal);
+        assert!(generators == vec![2]);
+        let mut generated = z6.generated_subgroup(&generators, 6).unwrap();
+        generated.sort();
+        assert!(generated == evens);
+    }
+
+    #[test]
+    fn subgroups_of_z6() {
+        let add = |a: u8, b: u8| (a + b) % 6;
+        let sub = |a: u8, b: u8| (a + 6 - b) % 6;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let z6 = Group::new(AlgaeSet::<u8>::all(), &mut op, 0);
+        let subgroups = z6.subgroups(&[0, 1, 2, 3, 4, 5]);
+        let mut orders: Vec<usize> = subgroups.iter().map(|h| h.len()).collect();
+        orders.sort();
+        assert!(orders == vec![1, 2, 3, 6]);
+        assert!(subgroups.iter().any(|h| h.len() == 3 && h.contains(&2) && h.contains(&4)));
+    }
+
+    #[test]
+    fn square_colorings() {
+        let add = |a: usize, b: usize| (a + b) % 4;
+        let sub = |a: usize, b: usize| (a + 4 - b) % 4;
+        let mut op = GroupOperation::new(&add, &sub, 0);
+        let rotations = Group::new(AlgaeSet::<usize>::all(), &mut op, 0);
+        let rotate = |r: usize, coloring: [u8; 4]| {
+            let mut rotated = [0; 4];
+            for (i, color) in coloring.iter().enumerate() {
+                rotated[(i + r) % 4] = *color;
+            }
+            rotated
+        };
+        let colorings: Vec<[u8; 4]> = (0..16u8)
+            .map(|bits| [bits & 1, (bits >> 1) & 1, (bits >> 2) & 1, (bits >> 3) & 1])
+            .collect();
+        assert!(rotations.count_orbits(&rotate, &[0, 1, 2, 3], &colorings) == Some(6));
+        assert!(rotations.count_orbits(&rotate, &[], &colorings).is_none());
+    }
+
+    #[test]
+    fn dihedral_semidirect_product() {
+        let mut add = GroupOperation::new(&|a: u8, b: u8| (a + b) % 4, &|a, b| (a + 4 - b) % 4, 0);
+        let mut flip = GroupOperation::new(&|a: u8, b: u8| a ^ b, &|a, b| a ^ b, 0);
+        let z4 = Group::new(AlgaeSet::of(|n: &u8| *n < 4), &mut add, 0);
+        let z2 = Group::new(AlgaeSet::of(|h: &u8| *h < 2), &mut flip, 0);
+        let reflect = |h: u8, n: u8| if h == 0 { n } else { (4 - n) % 4 };
+        let mut op = None;
+        let mut d4 = Group::semidirect_product(&z4, &z2, &reflect, &mut op);
+        assert!(d4.aset().has(&(3, 1)) && !d4.aset().has(&(1, 2)));
+
+        let elements: Vec<(u8, u8)> = (0..4).flat_map(|n| (0..2).map(move |h| (n, h))).collect();
+        let mut products = vec![];
+        for a in &elements {
+            for b in &elements {
+                let product = d4.with(*a, *b).unwrap();
+                assert!(elements.contains(&product));
+                if !products.contains(&product) {
+                    products.push(product);
+                }
+            }
+        }
+        assert!(products.len() == 8);
+        assert!(d4.with((1, 0), (0, 1)).unwrap() != d4.with((0, 1), (1, 0)).unwrap());
+    }
+}
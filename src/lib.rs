@@ -9,3 +9,5 @@ pub mod algaeset;
 pub mod magma;
 pub mod group;
 pub mod mapping;
+#[cfg(feature = "classify")]
+pub mod classify;
@@ -1,12 +1,387 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
 use crate::algaeset::AlgaeSet;
-use crate::mapping::{BinaryOperation, PropertyError, PropertyType};
+use crate::group::Group;
+use crate::mapping::{
+    binop_has_invertible_identity, binop_is_invertible, BinaryOperation, Counterexample,
+    PropertyError, PropertyType,
+};
+
+fn validate_over<T: Copy + PartialEq>(
+    binop: &dyn BinaryOperation<T>,
+    sample: &[T],
+) -> Result<(), Counterexample<T>> {
+    for property in binop.properties() {
+        if let Some(elements) = property.counterexample(binop.operation(), sample) {
+            return Err(Counterexample {
+                law: property.violation(),
+                elements,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The result of a property check bounded by [`with_timeout`](Magmoid::with_timeout).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Checked<T> {
+    /// Every property was checked over the operation's full input history
+    Verified(T),
+    /// Properties were only checked over a pseudorandom subset of the history
+    Inconclusive(T),
+}
+
+impl<T> Checked<T> {
+    /// Returns the product, however thoroughly it was checked
+    pub fn value(self) -> T {
+        match self {
+            Checked::Verified(value) | Checked::Inconclusive(value) => value,
+        }
+    }
+}
+
+fn pseudorandom_subset<T: Copy + PartialEq>(sample: &[T], operands: [T; 2], size: usize) -> Vec<T> {
+    let mut subset = operands.to_vec();
+    subset.dedup();
+    let mut candidates: Vec<T> = sample.iter().filter(|x| !operands.contains(x)).copied().collect();
+    let mut state = RandomState::new().build_hasher().finish();
+    while subset.len() < size && !candidates.is_empty() {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        subset.push(candidates.swap_remove((state >> 33) as usize % candidates.len()));
+    }
+    subset
+}
+
+/// Generates the precondition setters shared by every magmoid structure
+macro_rules! precondition_setters {
+    ($($structure:ident),+) => {
+        $(
+            impl<'a, T> $structure<'a, T> {
+                /// Makes `with` reject operands for which `precondition` fails
+                ///
+                /// This models partial operations, e.g. division by nonzero
+                /// divisors, without ever invoking the operation outside its
+                /// domain: failing operands give a
+                /// [`PreconditionError`](crate::mapping::PropertyError::PreconditionError).
+                pub fn set_precondition(&mut self, precondition: &'a dyn Fn(T, T) -> bool) {
+                    self.precondition = Some(precondition);
+                }
+
+                pub(crate) fn preconditioned(
+                    mut self,
+                    precondition: Option<&'a dyn Fn(T, T) -> bool>,
+                ) -> Self {
+                    self.precondition = precondition;
+                    self
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use precondition_setters;
+
+precondition_setters!(Magma, UnitalMagma, Groupoid, Quasigroup, Monoid, Loop);
+
+/// Generates `into_opposite` for magmoid structures, given the fields their
+/// constructors take after the set and operation
+macro_rules! opposite_conversions {
+    ($($structure:ident($($field:ident),*)),+) => {
+        $(
+            impl<'a, T: Copy + PartialEq + 'a> $structure<'a, T> {
+                /// Returns the same structure under the opposite operation `a ∘ b = b · a`
+                ///
+                /// Structures only borrow their operations, so the
+                /// [`OppositeOperation`](crate::mapping::OppositeOperation) is
+                /// kept in `opposite`. The set and identity carry over, but
+                /// any precondition is dropped, since it constrains operands
+                /// in their original order.
+                pub fn into_opposite<'b>(
+                    self,
+                    opposite: &'b mut Option<crate::mapping::OppositeOperation<'a, T>>,
+                ) -> $structure<'b, T>
+                where
+                    'a: 'b,
+                {
+                    let original: &'a dyn crate::mapping::BinaryOperation<T> = self.binop;
+                    let binop = opposite.insert(crate::mapping::OppositeOperation::new(original));
+                    $structure::new(self.aset, binop $(, self.$field)*)
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use opposite_conversions;
+
+opposite_conversions!(
+    Magma(),
+    UnitalMagma(identity),
+    Groupoid(),
+    Quasigroup(),
+    Monoid(identity),
+    Loop(identity)
+);
 
 pub trait Magmoid<T: Copy + PartialEq> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T>;
 
-    fn with(&mut self, left: T, right: T) -> Result<T, PropertyError> {
+    /// Returns the precondition operands must satisfy, if one has been set
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        None
+    }
+
+    fn with(&mut self, left: T, right: T) -> Result<T, Counterexample<T>> {
+        if self.precondition().is_some_and(|precondition| !(precondition)(left, right)) {
+            return Err(Counterexample {
+                law: PropertyError::PreconditionError,
+                elements: vec![left, right],
+            });
+        }
         self.binop().with(left, right)
     }
+
+    /// Returns the result of `with`, checking at most `max_checks` combinations
+    ///
+    /// Properties are checked over the largest subset of the input history
+    /// whose triples number at most `max_checks`, always including `left`
+    /// and `right`; the rest of the subset is drawn without replacement and
+    /// afresh on each call. If that subset is the whole history the result
+    /// is [`Checked::Verified`]; otherwise it is [`Checked::Inconclusive`],
+    /// since violations outside the subset go unnoticed. An operation that
+    /// records no history is only ever checked over the operands, and so is
+    /// always inconclusive.
+    fn with_timeout(
+        &mut self,
+        left: T,
+        right: T,
+        max_checks: usize,
+    ) -> Result<Checked<T>, Counterexample<T>> {
+        if self.precondition().is_some_and(|precondition| !(precondition)(left, right)) {
+            return Err(Counterexample {
+                law: PropertyError::PreconditionError,
+                elements: vec![left, right],
+            });
+        }
+        let binop = self.binop();
+        binop.cache(left);
+        binop.cache(right);
+        let mut size = 2;
+        while (size + 1) * (size + 1) * (size + 1) <= max_checks {
+            size += 1;
+        }
+        let history = binop.input_history();
+        let exhaustive = !history.is_empty() && history.len() <= size;
+        let subset = if exhaustive {
+            history.clone()
+        } else {
+            pseudorandom_subset(history, [left, right], size)
+        };
+        validate_over(binop, &subset)?;
+        let product = (binop.operation())(left, right);
+        if exhaustive {
+            Ok(Checked::Verified(product))
+        } else {
+            Ok(Checked::Inconclusive(product))
+        }
+    }
+
+    /// Returns the product of `left` and `right` without caching or checking properties
+    ///
+    /// This is the fast path for structures whose operations have already
+    /// been validated, e.g. by [`verify`](Magmoid::verify).
+    fn apply(&mut self, left: T, right: T) -> T {
+        (self.binop().operation())(left, right)
+    }
+
+    /// Returns the product of `elements` in order, combined pairwise as a balanced tree
+    ///
+    /// For an associative operation this agrees with a left fold, while
+    /// keeping the reduction depth logarithmic in the number of elements.
+    /// Products are evaluated without checking properties, and an empty
+    /// slice has no product.
+    fn reduce_tree(&mut self, elements: &[T]) -> Option<T> {
+        let op = self.binop().operation();
+        let mut level = elements.to_vec();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => (op)(*left, *right),
+                    _ => pair[0],
+                })
+                .collect();
+        }
+        level.first().copied()
+    }
+
+    /// Returns whether the operation agrees with `oracle` on every pair of `sample`
+    ///
+    /// This cross-checks a fast or optimized operation against a reference
+    /// implementation; products are evaluated without checking properties.
+    fn apply_checked_against(&mut self, oracle: &dyn Fn(T, T) -> T, sample: &[T]) -> bool {
+        let op = self.binop().operation();
+        sample
+            .iter()
+            .all(|a| sample.iter().all(|b| (op)(*a, *b) == (oracle)(*a, *b)))
+    }
+
+    /// Returns the left translation `x ↦ a·x` by `a`, evaluated without checking properties
+    fn left_translation<'b>(&'b mut self, a: T) -> impl Fn(T) -> T + 'b
+    where
+        T: 'b,
+    {
+        let op = self.binop().operation();
+        move |x| (op)(a, x)
+    }
+
+    /// Returns the right translation `x ↦ x·a` by `a`, evaluated without checking properties
+    fn right_translation<'b>(&'b mut self, a: T) -> impl Fn(T) -> T + 'b
+    where
+        T: 'b,
+    {
+        let op = self.binop().operation();
+        move |x| (op)(x, a)
+    }
+
+    /// Returns how long each of the operation's properties takes to check over `sample`
+    fn profile(&mut self, sample: &[T]) -> BTreeMap<&'static str, Duration> {
+        let binop = self.binop();
+        let mut timings = BTreeMap::new();
+        for property in binop.properties() {
+            let start = Instant::now();
+            property.holds_over(binop.operation(), sample);
+            timings.insert(property.name(), start.elapsed());
+        }
+        timings
+    }
+
+    /// Returns the first of `elements` acting as a two-sided identity on `elements`
+    fn find_identity(&mut self, elements: &[T]) -> Option<T> {
+        let op = self.binop().operation();
+        elements
+            .iter()
+            .find(|e| PropertyType::WithIdentity(**e).holds_over(op, elements))
+            .copied()
+    }
+
+    /// Returns whether `elements` consist of `identity` alone
+    fn is_trivial(&mut self, elements: &[T], identity: T) -> bool {
+        let op = self.binop().operation();
+        !elements.is_empty()
+            && elements.iter().all(|e| *e == identity)
+            && (op)(identity, identity) == identity
+    }
+
+    /// Returns whether every one of `elements` is a product of two of `elements`
+    fn is_surjective_over(&mut self, elements: &[T]) -> bool {
+        let op = self.binop().operation();
+        elements.iter().all(|c| {
+            elements
+                .iter()
+                .any(|a| elements.iter().any(|b| (op)(*a, *b) == *c))
+        })
+    }
+
+    /// Returns how `a` and `b` compare in the order induced by a meet-semilattice
+    ///
+    /// For a commutative, associative, idempotent operation `a ≤ b` exactly
+    /// when `a·b == a`, so operations like `min` induce their usual orders.
+    fn induced_order(&mut self, a: T, b: T) -> Option<Ordering> {
+        let meet = (self.binop().operation())(a, b);
+        if a == b {
+            Some(Ordering::Equal)
+        } else if meet == a {
+            Some(Ordering::Less)
+        } else if meet == b {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether at most one element of `sample` acts as an identity on `sample`
+    ///
+    /// Identities are unique for any well-behaved operation, so a `false`
+    /// result points to a buggy operation or an inconsistent [`PartialEq`].
+    fn has_unique_identity(&mut self, sample: &[T]) -> bool {
+        let op = self.binop().operation();
+        let identities: Vec<T> = sample
+            .iter()
+            .filter(|e| PropertyType::WithIdentity(**e).holds_over(op, sample))
+            .copied()
+            .collect();
+        identities.iter().all(|e| identities.iter().all(|f| e == f))
+    }
+
+    /// Returns a [`Magma`] over `subset`, provided the operation is closed over it
+    fn restrict_to(&mut self, subset: &[T]) -> Result<Magma<'_, T>, PropertyError>
+    where
+        T: 'static,
+    {
+        let binop = self.binop();
+        let op = binop.operation();
+        let closed = subset
+            .iter()
+            .all(|a| subset.iter().all(|b| subset.contains(&(op)(*a, *b))));
+        if !closed {
+            return Err(PropertyError::ClosureError);
+        }
+        let members = subset.to_vec();
+        Ok(Magma::new(AlgaeSet::of(move |x: &T| members.contains(x)), binop))
+    }
+
+    /// Returns whether each of the operation's properties holds over `sample`
+    fn verify(&mut self, sample: &[T]) -> Vec<(PropertyType<'_, T>, bool)> {
+        let binop = self.binop();
+        binop
+            .properties()
+            .into_iter()
+            .map(|property| {
+                let holds = property.holds_over(binop.operation(), sample);
+                (property, holds)
+            })
+            .collect()
+    }
+}
+
+/// A magmoid whose underlying set can be inspected
+///
+/// This is kept apart from [`Magmoid`] so that implementing the latter
+/// only requires exposing a binary operation.
+pub trait Carrier<T: Copy + PartialEq>: Magmoid<T> {
+    /// Returns the set the operation acts on
+    fn aset(&self) -> &AlgaeSet<T>;
+
+    /// Returns the first pair drawn from `sampler` whose product leaves the set
+    ///
+    /// Up to `trials` pairs are drawn, skipping any whose operands aren't
+    /// themselves members, and sampling stops at the first violation. `None`
+    /// means no violation was found in `trials` trials, which is evidence for
+    /// closure rather than a proof of it.
+    fn check_closure_statistically(
+        &mut self,
+        mut sampler: impl FnMut() -> T,
+        trials: usize,
+    ) -> Option<(T, T)> {
+        for _ in 0..trials {
+            let (a, b) = (sampler(), sampler());
+            if !self.aset().has(&a) || !self.aset().has(&b) {
+                continue;
+            }
+            let product = (self.binop().operation())(a, b);
+            if !self.aset().has(&product) {
+                return Some((a, b));
+            }
+        }
+        None
+    }
 }
 
 /// A set with an associated binary operation.
@@ -37,11 +412,62 @@ pub trait Magmoid<T: Copy + PartialEq> {
 pub struct Magma<'a, T> {
     aset: AlgaeSet<T>,
     binop: &'a mut dyn BinaryOperation<T>,
+    precondition: Option<&'a dyn Fn(T, T) -> bool>,
 }
 
 impl<'a, T> Magma<'a, T> {
     pub fn new(aset: AlgaeSet<T>, binop: &'a mut dyn BinaryOperation<T>) -> Self {
-        Self { aset, binop }
+        Self {
+            aset,
+            binop,
+            precondition: None,
+        }
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Magma<'a, T> {
+    /// Returns a [`Magma`], provided `binop` is closed over the members of `sample` in `aset`
+    ///
+    /// Otherwise, the error carries a pair of members whose product leaves `aset`.
+    pub fn new_checked(
+        aset: AlgaeSet<T>,
+        binop: &'a mut dyn BinaryOperation<T>,
+        sample: &[T],
+    ) -> Result<Self, Counterexample<T>> {
+        let op = binop.operation();
+        let members: Vec<T> = sample.iter().filter(|e| aset.has(e)).copied().collect();
+        for a in &members {
+            if let Some(b) = members.iter().find(|b| !aset.has(&(op)(*a, **b))) {
+                return Err(Counterexample {
+                    law: PropertyError::ClosureError,
+                    elements: vec![*a, *b],
+                });
+            }
+        }
+        Ok(Self::new(aset, binop))
+    }
+
+    /// Promotes the given magma to a [`Monoid`] with the given `identity`
+    ///
+    /// The operation must enforce associativity and identity existence, and
+    /// all of its properties must hold over `sample`.
+    pub fn try_into_monoid(
+        self,
+        identity: T,
+        sample: &[T],
+    ) -> Result<Monoid<'a, T>, PropertyError> {
+        if !self.binop.is(PropertyType::Associative) {
+            return Err(PropertyError::AssociativityError);
+        }
+        if !self.binop.is(PropertyType::WithIdentity(identity)) {
+            return Err(PropertyError::IdentityError);
+        }
+        validate_over(self.binop, sample)?;
+        let op = self.binop.operation();
+        if !PropertyType::WithIdentity(identity).holds_over(op, sample) {
+            return Err(PropertyError::IdentityError);
+        }
+        Ok(Monoid::new(self.aset, self.binop, identity).preconditioned(self.precondition))
     }
 }
 
@@ -49,6 +475,16 @@ impl<'a, T: Copy + PartialEq> Magmoid<T> for Magma<'a, T> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T> {
         self.binop
     }
+
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        self.precondition
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Carrier<T> for Magma<'a, T> {
+    fn aset(&self) -> &AlgaeSet<T> {
+        &self.aset
+    }
 }
 
 /// A set equipped with a binary operation and a specified identity element.
@@ -90,6 +526,7 @@ pub struct UnitalMagma<'a, T> {
     aset: AlgaeSet<T>,
     binop: &'a mut dyn BinaryOperation<T>,
     identity: T,
+    precondition: Option<&'a dyn Fn(T, T) -> bool>,
 }
 
 impl<'a, T: Copy + PartialEq> UnitalMagma<'a, T> {
@@ -99,6 +536,7 @@ impl<'a, T: Copy + PartialEq> UnitalMagma<'a, T> {
             aset,
             binop,
             identity,
+            precondition: None,
         }
     }
 }
@@ -107,11 +545,21 @@ impl<'a, T: Copy + PartialEq> Magmoid<T> for UnitalMagma<'a, T> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T> {
         self.binop
     }
+
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        self.precondition
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Carrier<T> for UnitalMagma<'a, T> {
+    fn aset(&self) -> &AlgaeSet<T> {
+        &self.aset
+    }
 }
 
 impl<'a, T> From<UnitalMagma<'a, T>> for Magma<'a, T> {
     fn from(magma: UnitalMagma<'a, T>) -> Magma<'a, T> {
-        Magma::new(magma.aset, magma.binop)
+        Magma::new(magma.aset, magma.binop).preconditioned(magma.precondition)
     }
 }
 
@@ -145,21 +593,26 @@ impl<'a, T> From<UnitalMagma<'a, T>> for Magma<'a, T> {
 ///     &mut div,
 /// );
 ///
-/// let ok_dividend = bad_groupoid.with(1.0, 2.0);
-/// assert!(ok_dividend.is_ok());
-/// assert!(ok_dividend.unwrap() == 0.5);
+/// // (1 / 1) / 2 != 1 / (1 / 2)
+/// let err_dividend = bad_groupoid.with(1.0, 2.0);
+/// assert!(err_dividend.is_err());
 /// let err_dividend = bad_groupoid.with(3.0, 6.0);
 /// assert!(err_dividend.is_err());
 /// ```
 pub struct Groupoid<'a, T> {
     aset: AlgaeSet<T>,
     binop: &'a mut dyn BinaryOperation<T>,
+    precondition: Option<&'a dyn Fn(T, T) -> bool>,
 }
 
 impl<'a, T: Copy + PartialEq> Groupoid<'a, T> {
     pub fn new(aset: AlgaeSet<T>, binop: &'a mut dyn BinaryOperation<T>) -> Self {
         assert!(binop.is(PropertyType::Associative));
-        Self { aset, binop }
+        Self {
+            aset,
+            binop,
+            precondition: None,
+        }
     }
 }
 
@@ -167,11 +620,21 @@ impl<'a, T: Copy + PartialEq> Magmoid<T> for Groupoid<'a, T> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T> {
         self.binop
     }
+
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        self.precondition
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Carrier<T> for Groupoid<'a, T> {
+    fn aset(&self) -> &AlgaeSet<T> {
+        &self.aset
+    }
 }
 
 impl<'a, T> From<Groupoid<'a, T>> for Magma<'a, T> {
     fn from(groupoid: Groupoid<'a, T>) -> Magma<'a, T> {
-        Magma::new(groupoid.aset, groupoid.binop)
+        Magma::new(groupoid.aset, groupoid.binop).preconditioned(groupoid.precondition)
     }
 }
 
@@ -201,12 +664,17 @@ impl<'a, T> From<Groupoid<'a, T>> for Magma<'a, T> {
 pub struct Quasigroup<'a, T> {
     aset: AlgaeSet<T>,
     binop: &'a mut dyn BinaryOperation<T>,
+    precondition: Option<&'a dyn Fn(T, T) -> bool>,
 }
 
 impl<'a, T: Copy + PartialEq> Quasigroup<'a, T> {
     pub fn new(aset: AlgaeSet<T>, binop: &'a mut dyn BinaryOperation<T>) -> Self {
         assert!(binop.is(PropertyType::Cancellative));
-        Self { aset, binop }
+        Self {
+            aset,
+            binop,
+            precondition: None,
+        }
     }
 }
 
@@ -214,11 +682,21 @@ impl<'a, T: Copy + PartialEq> Magmoid<T> for Quasigroup<'a, T> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T> {
         self.binop
     }
+
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        self.precondition
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Carrier<T> for Quasigroup<'a, T> {
+    fn aset(&self) -> &AlgaeSet<T> {
+        &self.aset
+    }
 }
 
 impl<'a, T> From<Quasigroup<'a, T>> for Magma<'a, T> {
     fn from(quasi: Quasigroup<'a, T>) -> Magma<'a, T> {
-        Magma::new(quasi.aset, quasi.binop)
+        Magma::new(quasi.aset, quasi.binop).preconditioned(quasi.precondition)
     }
 }
 
@@ -261,6 +739,7 @@ pub struct Monoid<'a, T> {
     aset: AlgaeSet<T>,
     binop: &'a mut dyn BinaryOperation<T>,
     identity: T,
+    precondition: Option<&'a dyn Fn(T, T) -> bool>,
 }
 
 impl<'a, T: Copy + PartialEq> Monoid<'a, T> {
@@ -271,7 +750,47 @@ impl<'a, T: Copy + PartialEq> Monoid<'a, T> {
             aset,
             binop,
             identity,
+            precondition: None,
+        }
+    }
+
+    /// Returns at most `bound` elements of the submonoid generated by `generators`
+    ///
+    /// Elements are produced breadth-first, starting from the identity, so
+    /// shorter products of generators always appear before longer ones.
+    pub fn submonoid_generated_by(&mut self, generators: &[T], bound: usize) -> Vec<T> {
+        let op = self.binop.operation();
+        let mut elements = vec![self.identity];
+        let mut frontier = 0;
+        while frontier < elements.len() && elements.len() < bound {
+            let element = elements[frontier];
+            for generator in generators {
+                let product = (op)(element, *generator);
+                if !elements.contains(&product) {
+                    elements.push(product);
+                }
+            }
+            frontier += 1;
         }
+        elements.truncate(bound);
+        elements
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Monoid<'a, T> {
+    /// Promotes the given monoid to a [`Group`]
+    ///
+    /// The operation must enforce invertibility with the monoid's identity,
+    /// and all of its properties must hold over `sample`.
+    pub fn try_into_group(self, sample: &[T]) -> Result<Group<'a, T>, PropertyError> {
+        if !binop_is_invertible(self.binop) {
+            return Err(PropertyError::InvertibilityError);
+        }
+        if !binop_has_invertible_identity(self.binop, self.identity) {
+            return Err(PropertyError::IdentityError);
+        }
+        validate_over(self.binop, sample)?;
+        Ok(Group::new(self.aset, self.binop, self.identity).preconditioned(self.precondition))
     }
 }
 
@@ -279,23 +798,34 @@ impl<'a, T: Copy + PartialEq> Magmoid<T> for Monoid<'a, T> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T> {
         self.binop
     }
+
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        self.precondition
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Carrier<T> for Monoid<'a, T> {
+    fn aset(&self) -> &AlgaeSet<T> {
+        &self.aset
+    }
 }
 
 impl<'a, T: Copy + PartialEq> From<Monoid<'a, T>> for Magma<'a, T> {
     fn from(monoid: Monoid<'a, T>) -> Magma<'a, T> {
-        Magma::new(monoid.aset, monoid.binop)
+        Magma::new(monoid.aset, monoid.binop).preconditioned(monoid.precondition)
     }
 }
 
 impl<'a, T: Copy + PartialEq> From<Monoid<'a, T>> for Groupoid<'a, T> {
     fn from(monoid: Monoid<'a, T>) -> Groupoid<'a, T> {
-        Groupoid::new(monoid.aset, monoid.binop)
+        Groupoid::new(monoid.aset, monoid.binop).preconditioned(monoid.precondition)
     }
 }
 
 impl<'a, T: Copy + PartialEq> From<Monoid<'a, T>> for UnitalMagma<'a, T> {
     fn from(monoid: Monoid<'a, T>) -> UnitalMagma<'a ,T> {
         UnitalMagma::new(monoid.aset, monoid.binop, monoid.identity)
+            .preconditioned(monoid.precondition)
     }
 }
 
@@ -327,6 +857,7 @@ pub struct Loop<'a, T> {
     aset: AlgaeSet<T>,
     binop: &'a mut dyn BinaryOperation<T>,
     identity: T,
+    precondition: Option<&'a dyn Fn(T, T) -> bool>,
 }
 
 impl<'a, T: Copy + PartialEq> Loop<'a, T> {
@@ -337,6 +868,7 @@ impl<'a, T: Copy + PartialEq> Loop<'a, T> {
             aset,
             binop,
             identity,
+            precondition: None,
         }
     }
 }
@@ -345,22 +877,382 @@ impl<'a, T: Copy + PartialEq> Magmoid<T> for Loop<'a, T> {
     fn binop(&mut self) -> &mut dyn BinaryOperation<T> {
         self.binop
     }
+
+    fn precondition(&self) -> Option<&dyn Fn(T, T) -> bool> {
+        self.precondition
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Carrier<T> for Loop<'a, T> {
+    fn aset(&self) -> &AlgaeSet<T> {
+        &self.aset
+    }
 }
 
 impl<'a, T: Copy + PartialEq> From<Loop<'a, T>> for Magma<'a, T> {
     fn from(loop_: Loop<'a, T>) -> Magma<'a, T> {
-        Magma::new(loop_.aset, loop_.binop)
+        Magma::new(loop_.aset, loop_.binop).preconditioned(loop_.precondition)
     }
 }
 
 impl<'a, T: Copy + PartialEq> From<Loop<'a, T>> for UnitalMagma<'a, T> {
     fn from(loop_: Loop<'a, T>) -> UnitalMagma<'a, T> {
-        UnitalMagma::new(loop_.aset, loop_.binop, loop_.identity)
+        UnitalMagma::new(loop_.aset, loop_.binop, loop_.identity).preconditioned(loop_.precondition)
     }
 }
 
 impl<'a, T: Copy + PartialEq> From<Loop<'a, T>> for Quasigroup<'a, T> {
     fn from(loop_: Loop<'a, T>) -> Quasigroup<'a, T> {
-        Quasigroup::new(loop_.aset, loop_.binop)
+        Quasigroup::new(loop_.aset, loop_.binop).preconditioned(loop_.precondition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::mapping::{
+        AbelianOperation, AssociativeOperation, CancellativeOperation, GroupOperation,
+        MonoidOperation,
+    };
+
+    struct Division {
+        history: Vec<i32>,
+    }
+
+    impl BinaryOperation<i32> for Division {
+        fn operation(&self) -> &dyn Fn(i32, i32) -> i32 {
+            &|a, b| a / b
+        }
+
+        fn properties(&self) -> Vec<PropertyType<'_, i32>> {
+            vec![]
+        }
+
+        fn input_history(&self) -> &Vec<i32> {
+            &self.history
+        }
+
+        fn cache(&mut self, input: i32) {
+            self.history.push(input);
+        }
+    }
+
+    #[test]
+    fn precondition_rejects_zero_divisor() {
+        let mut div = Division { history: vec![] };
+        let nonzero_divisor = |_: i32, b: i32| b != 0;
+        let mut magma = Magma::new(AlgaeSet::<i32>::all(), &mut div);
+        magma.set_precondition(&nonzero_divisor);
+        let quotient = magma.with(6, 2);
+        assert!(quotient.is_ok());
+        assert!(quotient.unwrap() == 3);
+        let bad_quotient = magma.with(6, 0);
+        let failure = bad_quotient.unwrap_err();
+        assert!(matches!(failure.law, PropertyError::PreconditionError));
+        assert!(failure.elements == vec![6, 0]);
+        assert!(magma.binop().input_history() == &vec![6, 2]);
+    }
+
+    #[test]
+    fn apply_skips_history() {
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| a + b);
+        let mut magma = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        assert!(magma.with(2, 3).unwrap() == magma.apply(2, 3));
+        assert!(magma.apply(4, 5) == 9);
+        assert!(magma.binop().input_history().len() == 2);
+    }
+
+    #[test]
+    fn history_disabled() {
+        let mut add = MonoidOperation::new(&|a: u64, b: u64| a + b, 0).without_history();
+        let mut monoid = Monoid::new(AlgaeSet::<u64>::all(), &mut add, 0);
+        let mut total = 0;
+        for x in 0..100_000 {
+            total = monoid.with(total, x).unwrap();
+        }
+        assert!(total == 4_999_950_000);
+        assert!(monoid.binop().input_history().is_empty());
+
+        let mut bad_add = MonoidOperation::new(&|a: u64, b: u64| a + b + 1, 0).without_history();
+        let mut bad_monoid = Monoid::new(AlgaeSet::<u64>::all(), &mut bad_add, 0);
+        assert!(bad_monoid.with(1, 2).is_err());
+    }
+
+    #[test]
+    fn checked_closure() {
+        let z5 = || AlgaeSet::<i32>::of(|x| (0..5).contains(x));
+        let sample: Vec<i32> = (-5..10).collect();
+        let mut add_mod_5 = AbelianOperation::new(&|a: i32, b: i32| (a + b) % 5);
+        assert!(Magma::new_checked(z5(), &mut add_mod_5, &sample).is_ok());
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| a + b);
+        let escaping = Magma::new_checked(z5(), &mut add, &sample);
+        let failure = escaping.err().unwrap();
+        assert!(matches!(failure.law, PropertyError::ClosureError));
+        assert!(failure.elements == vec![1, 4]);
+    }
+
+    #[test]
+    fn tree_reduction() {
+        type Matrix = [[u64; 2]; 2];
+        let mul = |a: Matrix, b: Matrix| {
+            let entry = |i: usize, j: usize| {
+                a[i][0].wrapping_mul(b[0][j]).wrapping_add(a[i][1].wrapping_mul(b[1][j]))
+            };
+            [[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]]
+        };
+        let mut op = AssociativeOperation::new(&mul);
+        let mut groupoid = Groupoid::new(AlgaeSet::<Matrix>::all(), &mut op);
+        let elements: Vec<Matrix> = (0..37).map(|k| [[1, k], [k % 3, 2]]).collect();
+        let folded = elements[1..].iter().fold(elements[0], |acc, m| mul(acc, *m));
+        assert!(groupoid.reduce_tree(&elements) == Some(folded));
+        assert!(groupoid.reduce_tree(&elements[..1]) == Some(elements[0]));
+        assert!(groupoid.reduce_tree(&[]).is_none());
+    }
+
+    #[test]
+    fn statistical_closure() {
+        let mut state = 7_u32;
+        let mut sampler = move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 16) as i32 % 10
+        };
+        let digits = || AlgaeSet::<i32>::of(|x| (0..10).contains(x));
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| a + b);
+        let mut escaping = Magma::new(digits(), &mut add);
+        let (a, b) = escaping.check_closure_statistically(&mut sampler, 1_000).unwrap();
+        assert!(a + b >= 10);
+
+        let mut add_mod_10 = AbelianOperation::new(&|a: i32, b: i32| (a + b) % 10);
+        let mut closed = Magma::new(digits(), &mut add_mod_10);
+        assert!(closed.check_closure_statistically(&mut sampler, 1_000).is_none());
+    }
+
+    #[test]
+    fn oracle_agreement() {
+        let oracle = |a: u8, b: u8| (a + b) % 7;
+        let sample: Vec<u8> = (0..7).collect();
+        let mut fast_add = AbelianOperation::new(&|a: u8, b: u8| {
+            let sum = a + b;
+            if sum >= 7 {
+                sum - 7
+            } else {
+                sum
+            }
+        });
+        let mut z7 = Magma::new(AlgaeSet::<u8>::all(), &mut fast_add);
+        assert!(z7.apply_checked_against(&oracle, &sample));
+
+        let mut buggy_add = AbelianOperation::new(&|a: u8, b: u8| {
+            let sum = a + b;
+            if sum > 7 {
+                sum - 7
+            } else {
+                sum
+            }
+        });
+        let mut buggy_z7 = Magma::new(AlgaeSet::<u8>::all(), &mut buggy_add);
+        assert!(!buggy_z7.apply_checked_against(&oracle, &sample));
+    }
+
+    #[test]
+    fn bounded_checks() {
+        let calls = Cell::new(0);
+        let counted = |a: i64, b: i64| {
+            calls.set(calls.get() + 1);
+            a - b
+        };
+        let mut sub = AssociativeOperation::new(&counted);
+        let mut magma = Magma::new(AlgaeSet::<i64>::all(), &mut sub);
+        for x in 0..10_000 {
+            magma.binop().cache(x);
+        }
+        let difference = magma.with_timeout(7, 3, 1_000);
+        assert!(calls.get() <= 4 * 1_000);
+        assert!(matches!(difference.unwrap_err().law, PropertyError::AssociativityError));
+
+        let mut add = AssociativeOperation::new(&|a: i64, b: i64| a + b);
+        let mut magma = Magma::new(AlgaeSet::<i64>::all(), &mut add);
+        assert!(magma.with_timeout(1, 2, 1_000).unwrap() == Checked::Verified(3));
+        for x in 0..10_000 {
+            magma.binop().cache(x);
+        }
+        let sum = magma.with_timeout(7, 3, 1_000).unwrap();
+        assert!(sum == Checked::Inconclusive(10));
+        assert!(sum.value() == 10);
+
+        let mut add = AssociativeOperation::new(&|a: i64, b: i64| a + b).without_history();
+        let mut magma = Magma::new(AlgaeSet::<i64>::all(), &mut add);
+        assert!(magma.with_timeout(1, 2, 1_000).unwrap() == Checked::Inconclusive(3));
+    }
+
+    #[test]
+    fn translations_are_bijections() {
+        let mut add = GroupOperation::new(&|a: u8, b: u8| (a + b) % 5, &|a, b| (a + 5 - b) % 5, 0);
+        let mut z5 = Group::new(AlgaeSet::<u8>::all(), &mut add, 0);
+        let elements = [0, 1, 2, 3, 4];
+        let left = z5.left_translation(2);
+        let mut images: Vec<u8> = elements.iter().map(|x| left(*x)).collect();
+        assert!(images == vec![2, 3, 4, 0, 1]);
+        images.sort();
+        assert!(images == elements);
+
+        let mut sub = CancellativeOperation::new(&|a: u8, b: u8| (a + 5 - b) % 5);
+        let mut magma = Magma::new(AlgaeSet::<u8>::all(), &mut sub);
+        let right = magma.right_translation(1);
+        assert!(elements.iter().map(|x| right(*x)).collect::<Vec<u8>>() == vec![4, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn verify_reports_each_property() {
+        let mut sub = GroupOperation::new(&|a: i32, b: i32| a - b, &|a, b| a + b, 0);
+        let mut magma = Magma::new(AlgaeSet::<i32>::all(), &mut sub);
+        let report = magma.verify(&[1, 2, 3]);
+        assert!(report.len() == 3);
+        assert!(report.iter().all(|(_, holds)| !holds));
+
+        let mut add = GroupOperation::new(&|a: i32, b: i32| a + b, &|a, b| a * b, 0);
+        let mut magma = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        let report = magma.verify(&[1, 2, 3]);
+        for (property, holds) in report {
+            match property {
+                PropertyType::Invertible(_, _) => assert!(!holds),
+                _ => assert!(holds),
+            }
+        }
+    }
+
+    #[test]
+    fn numerical_submonoid() {
+        let mut add = MonoidOperation::new(&|a: i32, b: i32| a + b, 0);
+        let mut naturals = Monoid::new(AlgaeSet::<i32>::of(|&x| x >= 0), &mut add, 0);
+        let generated = naturals.submonoid_generated_by(&[2, 3], 8);
+        assert!(generated == vec![0, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(!generated.contains(&1));
+    }
+
+    #[test]
+    fn finds_identity() {
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| (a + b) % 5);
+        let mut z5 = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        assert!(z5.find_identity(&[1, 2, 3, 4, 0]) == Some(0));
+
+        let mut sub = CancellativeOperation::new(&|a: i32, b: i32| (a - b).rem_euclid(5));
+        let mut z5 = Magma::new(AlgaeSet::<i32>::all(), &mut sub);
+        assert!(z5.find_identity(&[0, 1, 2, 3, 4]).is_none());
+    }
+
+    #[test]
+    fn duplicate_identities() {
+        #[derive(Clone, Copy)]
+        struct Approx(f64);
+        impl PartialEq for Approx {
+            fn eq(&self, other: &Self) -> bool {
+                (self.0 - other.0).abs() < 0.1
+            }
+        }
+        let mut mean = AbelianOperation::new(&|a: Approx, b: Approx| Approx((a.0 + b.0) / 2.0));
+        let mut magma = Magma::new(AlgaeSet::<Approx>::all(), &mut mean);
+        assert!(magma.has_unique_identity(&[Approx(0.0), Approx(0.05)]));
+        assert!(!magma.has_unique_identity(&[Approx(0.0), Approx(0.15)]));
+    }
+
+    #[test]
+    fn trivial_subgroup() {
+        let mut add = GroupOperation::new(&|a: u8, b: u8| (a + b) % 6, &|a, b| (a + 6 - b) % 6, 0);
+        let mut z6 = Group::new(AlgaeSet::<u8>::all(), &mut add, 0);
+        assert!(z6.is_trivial(&[0], 0));
+        assert!(!z6.is_trivial(&[0, 1, 2, 3, 4, 5], 0));
+        assert!(!z6.is_trivial(&[], 0));
+    }
+
+    #[test]
+    fn surjective_operations() {
+        let mut add = GroupOperation::new(&|a: u8, b: u8| (a + b) % 6, &|a, b| (a + 6 - b) % 6, 0);
+        let mut z6 = Group::new(AlgaeSet::<u8>::all(), &mut add, 0);
+        assert!(z6.is_surjective_over(&[0, 1, 2, 3, 4, 5]));
+
+        let mut constant = AbelianOperation::new(&|_: u8, _: u8| 0);
+        let mut magma = Magma::new(AlgaeSet::<u8>::all(), &mut constant);
+        assert!(!magma.is_surjective_over(&[0, 1, 2]));
+        assert!(magma.is_surjective_over(&[0]));
+    }
+
+    #[test]
+    fn semilattice_orders() {
+        let mut min = AbelianOperation::new(&|a: u32, b: u32| a.min(b));
+        let mut integers = Magma::new(AlgaeSet::<u32>::all(), &mut min);
+        assert!(integers.induced_order(2, 5) == Some(Ordering::Less));
+        assert!(integers.induced_order(5, 2) == Some(Ordering::Greater));
+        assert!(integers.induced_order(3, 3) == Some(Ordering::Equal));
+
+        let gcd = |mut a: u32, mut b: u32| {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        };
+        let mut meet = AbelianOperation::new(&gcd);
+        let mut divisors = Magma::new(AlgaeSet::<u32>::all(), &mut meet);
+        assert!(divisors.induced_order(2, 6) == Some(Ordering::Less));
+        assert!(divisors.induced_order(4, 6).is_none());
+    }
+
+    #[test]
+    fn promote_and_demote() {
+        let sample = [1, 2, 3];
+        let mut add = GroupOperation::new(&|a: i32, b: i32| a + b, &|a, b| a - b, 0);
+        let magma = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        let monoid = magma.try_into_monoid(0, &sample).unwrap();
+        let mut group = monoid.try_into_group(&sample).unwrap();
+        assert!(group.with(1, -1).unwrap() == 0);
+        let mut magma: Magma<'_, i32> = group.into();
+        assert!(magma.with(1, 2).unwrap() == 3);
+    }
+
+    #[test]
+    fn failed_promotions() {
+        let sample = [1, 2, 3];
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| a + b);
+        let magma = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        let monoid = magma.try_into_monoid(0, &sample);
+        assert!(matches!(monoid, Err(PropertyError::AssociativityError)));
+
+        let mut add = MonoidOperation::new(&|a: i32, b: i32| a + b, 0);
+        let magma = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        let monoid = magma.try_into_monoid(1, &sample);
+        assert!(matches!(monoid, Err(PropertyError::IdentityError)));
+
+        let mut add = MonoidOperation::new(&|a: i32, b: i32| a + b, 0);
+        let monoid = Monoid::new(AlgaeSet::<i32>::all(), &mut add, 0);
+        let group = monoid.try_into_group(&sample);
+        assert!(matches!(group, Err(PropertyError::InvertibilityError)));
+    }
+
+    #[test]
+    fn profile_times_each_property() {
+        let sample: Vec<i32> = (0..20).collect();
+        let mut add = GroupOperation::new(&|a: i32, b: i32| a + b, &|a, b| a - b, 0);
+        let mut magma = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        let timings = magma.profile(&sample);
+        let properties: Vec<&str> = timings.keys().copied().collect();
+        assert!(properties == vec!["associative", "identity", "invertible"]);
+
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| a + b);
+        let mut magma = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        let timings = magma.profile(&sample);
+        let properties: Vec<&str> = timings.keys().copied().collect();
+        assert!(properties == vec!["abelian", "commutative"]);
+    }
+
+    #[test]
+    fn restrict_to_subset() {
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| (a + b) % 6);
+        let mut z6 = Magma::new(AlgaeSet::<i32>::all(), &mut add);
+        let mut evens = z6.restrict_to(&[0, 2, 4]).unwrap();
+        assert!(evens.with(2, 4).unwrap() == 0);
+        assert!(matches!(z6.restrict_to(&[0, 1, 4]), Err(PropertyError::ClosureError)));
     }
 }
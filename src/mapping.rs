@@ -1,32 +1,125 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Returns every ordered selection of `group_size` distinct positions in `collection`
 fn permutations<T: Clone>(collection: &[T], group_size: usize) -> Vec<Vec<T>> {
+    if group_size == 0 {
+        return vec![vec![]];
+    }
     let mut groupings: Vec<Vec<T>> = vec![];
-    for chunk in collection.chunks(group_size) {
-        if chunk.len() != group_size {
-            continue;
+    for (i, first) in collection.iter().enumerate() {
+        let mut rest = collection.to_vec();
+        rest.remove(i);
+        for tail in permutations(&rest, group_size - 1) {
+            let mut grouping = vec![first.clone()];
+            grouping.extend(tail);
+            groupings.push(grouping);
         }
-        groupings.push(chunk.to_vec());
     }
-    let mut reversed_collection = collection.to_vec();
-    reversed_collection.reverse();
-    for chunk in reversed_collection.chunks(group_size) {
-        if chunk.len() != group_size {
-            continue;
+    groupings
+}
+
+/// Returns every ordered selection of `power` elements of `collection`, with repetition
+///
+/// Laws are checked over these tuples, since many break only when an
+/// element is repeated, as in `(a·a)·b` against `a·(a·b)`.
+fn cartesian_power<T: Clone>(collection: &[T], power: usize) -> Vec<Vec<T>> {
+    let mut tuples: Vec<Vec<T>> = vec![vec![]];
+    for _ in 0..power {
+        tuples = tuples
+            .into_iter()
+            .flat_map(|tuple| {
+                collection.iter().map(move |x| {
+                    let mut longer = tuple.clone();
+                    longer.push(x.clone());
+                    longer
+                })
+            })
+            .collect();
+    }
+    tuples
+}
+
+/// Returns `operands` followed by the latest other inputs in `history`, `cap` in all
+///
+/// The operands are always included, even past `cap`, since every call
+/// checks its own operands.
+fn capped_sample<T: Copy + PartialEq>(history: &[T], operands: [T; 2], cap: usize) -> Vec<T> {
+    let mut sample = operands.to_vec();
+    sample.dedup();
+    for input in history.iter().rev() {
+        if sample.len() >= cap {
+            break;
+        }
+        if !sample.contains(input) {
+            sample.push(*input);
         }
-        groupings.push(chunk.to_vec());
     }
-    groupings
+    sample
+}
+
+/// Inputs seen by an operation, erasing the `Eq + Hash` bounds of their lookup
+trait InputSet<T> {
+    /// Adds `input`, returning whether it wasn't already present
+    fn insert(&mut self, input: T) -> bool;
+}
+
+impl<T: Eq + Hash> InputSet<T> for HashSet<T> {
+    fn insert(&mut self, input: T) -> bool {
+        HashSet::insert(self, input)
+    }
+}
+
+/// Caches `input` at the end of `history`, unless it's already there
+///
+/// Inputs are looked up in `hashed` when it's present, and by scanning
+/// `history` otherwise.
+fn record<'a, T: Copy + PartialEq>(
+    history: &mut Vec<T>,
+    hashed: &mut Option<Box<dyn InputSet<T> + 'a>>,
+    input: T,
+) {
+    let unseen = match hashed {
+        Some(hashed) => hashed.insert(input),
+        None => !history.contains(&input),
+    };
+    if unseen {
+        history.push(input);
+    }
 }
 
-fn cayley_product<T: Copy>(collection: &Vec<T>) -> Vec<Vec<T>> {
+/// Returns every ordered pair of elements of `collection`, as two-element vectors
+pub fn cayley_product<T: Clone>(collection: &[T]) -> Vec<Vec<T>> {
     let mut pairs: Vec<Vec<T>> = vec![];
     for x in collection {
         for y in collection {
-            pairs.push(vec![*x, *y]);
+            pairs.push(vec![x.clone(), y.clone()]);
         }
     }
     pairs
 }
 
+/// Returns whether `mul` distributes over `add` on both sides, for every triple in `sample`
+///
+/// Both `a*(b+c) == a*b + a*c` and `(b+c)*a == b*a + c*a` are checked,
+/// including triples with repeated elements.
+pub fn is_distributive_over<T: Copy + PartialEq>(
+    mul: &dyn Fn(T, T) -> T,
+    add: &dyn Fn(T, T) -> T,
+    sample: &[T],
+) -> bool {
+    sample.iter().all(|a| {
+        sample.iter().all(|b| {
+            sample.iter().all(|c| {
+                let left = (mul)(*a, (add)(*b, *c)) == (add)((mul)(*a, *b), (mul)(*a, *c));
+                let right = (mul)((add)(*b, *c), *a) == (add)((mul)(*b, *a), (mul)(*c, *a));
+                left && right
+            })
+        })
+    })
+}
+
 #[derive(Debug)]
 pub enum PropertyError {
     CommutativityError,
@@ -34,20 +127,63 @@ pub enum PropertyError {
     CancellativityError,
     IdentityError,
     InvertibilityError,
+    InvolutivityError,
+    IdempotencyError,
+    ClosureError,
+    PreconditionError,
     Other(String),
 }
 
-impl std::fmt::Display for PropertyError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+impl PropertyError {
+    /// Returns the message describing the given error
+    pub fn message(&self) -> Cow<'_, str> {
         let msg = match self {
             PropertyError::CommutativityError => "Operation is not commutative!",
             PropertyError::AssociativityError => "Operation is not associative!",
             PropertyError::CancellativityError => "Operation is not cancellative!",
             PropertyError::IdentityError => "Operation has no valid identity!",
             PropertyError::InvertibilityError => "Operation is not invertible!",
+            PropertyError::InvolutivityError => "Operation does not respect its involution!",
+            PropertyError::IdempotencyError => "Operation is not idempotent!",
+            PropertyError::ClosureError => "Operation is not closed over its set!",
+            PropertyError::PreconditionError => "Operands fail the operation's precondition!",
             PropertyError::Other(error) => error,
         };
-        write!(f, "{msg}")
+        Cow::Borrowed(msg)
+    }
+}
+
+impl std::fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl From<&str> for PropertyError {
+    fn from(message: &str) -> Self {
+        PropertyError::Other(String::from(message))
+    }
+}
+
+impl From<String> for PropertyError {
+    fn from(message: String) -> Self {
+        PropertyError::Other(message)
+    }
+}
+
+/// A law broken by an operation, along with the elements breaking it.
+///
+/// Identity violations carry no elements when there is no single witness
+/// to the lack of an identity.
+#[derive(Debug)]
+pub struct Counterexample<T> {
+    pub law: PropertyError,
+    pub elements: Vec<T>,
+}
+
+impl<T> From<Counterexample<T>> for PropertyError {
+    fn from(counterexample: Counterexample<T>) -> Self {
+        counterexample.law
     }
 }
 
@@ -58,85 +194,157 @@ pub enum PropertyType<'a, T> {
     Cancellative,
     WithIdentity(T),
     Invertible(T, &'a dyn Fn(T, T) -> T),
+    Involutive(&'a dyn Fn(T) -> T),
+    Idempotent,
 }
 
 impl<'a, T: Copy + PartialEq> PropertyType<'a, T> {
-    pub fn holds_over(&self, op: &dyn Fn(T, T) -> T, domain_sample: &Vec<T>) -> bool {
+    pub fn holds_over(&self, op: &dyn Fn(T, T) -> T, domain_sample: &[T]) -> bool {
+        self.counterexample(op, domain_sample).is_none()
+    }
+
+    /// Returns elements of `domain_sample` witnessing a failure of the property, if any
+    pub fn counterexample(&self, op: &dyn Fn(T, T) -> T, domain_sample: &[T]) -> Option<Vec<T>> {
         match self {
-            Self::Commutative | Self::Abelian => Self::commutativity_holds_over(op, domain_sample),
-            Self::Associative => Self::associativity_holds_over(op, domain_sample),
-            Self::Cancellative => Self::cancellative_holds_over(op, domain_sample),
-            Self::WithIdentity(identity) => Self::identity_holds_over(op, domain_sample, *identity),
+            Self::Commutative | Self::Abelian => {
+                Self::commutativity_counterexample(op, domain_sample)
+            }
+            Self::Associative => Self::associativity_counterexample(op, domain_sample),
+            Self::Cancellative => Self::cancellativity_counterexample(op, domain_sample),
+            Self::WithIdentity(identity) => {
+                Self::identity_counterexample(op, domain_sample, *identity)
+            }
             Self::Invertible(identity, inv) => {
-                Self::invertibility_holds_over(op, inv, domain_sample, *identity)
+                Self::invertibility_counterexample(op, inv, domain_sample, *identity)
             }
+            Self::Involutive(involution) => {
+                Self::involutivity_counterexample(op, involution, domain_sample)
+            }
+            Self::Idempotent => Self::idempotency_counterexample(op, domain_sample),
         }
     }
 
-    fn commutativity_holds_over(op: &dyn Fn(T, T) -> T, domain_sample: &Vec<T>) -> bool {
-        if domain_sample.len() < 2 {
-            return true;
+    /// Returns a short, human-readable name for the property
+    ///
+    /// Names are distinct for each variant, so they can key per-property
+    /// reports like [`profile`](crate::magma::Magmoid::profile).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Commutative => "commutative",
+            Self::Abelian => "abelian",
+            Self::Associative => "associative",
+            Self::Cancellative => "cancellative",
+            Self::WithIdentity(_) => "identity",
+            Self::Invertible(_, _) => "invertible",
+            Self::Involutive(_) => "involutive",
+            Self::Idempotent => "idempotent",
         }
-        return permutations(domain_sample, 2).iter().all(|pair| {
+    }
+
+    /// Returns the error reported when the property fails to hold
+    pub fn violation(&self) -> PropertyError {
+        match self {
+            Self::Commutative | Self::Abelian => PropertyError::CommutativityError,
+            Self::Associative => PropertyError::AssociativityError,
+            Self::Cancellative => PropertyError::CancellativityError,
+            Self::WithIdentity(_) => PropertyError::IdentityError,
+            Self::Invertible(_, _) => PropertyError::InvertibilityError,
+            Self::Involutive(_) => PropertyError::InvolutivityError,
+            Self::Idempotent => PropertyError::IdempotencyError,
+        }
+    }
+
+    fn commutativity_counterexample(op: &dyn Fn(T, T) -> T, domain_sample: &[T]) -> Option<Vec<T>> {
+        cartesian_power(domain_sample, 2).into_iter().find(|pair| {
             let left = (op)(pair[0], pair[1]);
             let right = (op)(pair[1], pair[0]);
-            left == right
-        });
+            left != right
+        })
     }
 
-    fn associativity_holds_over(op: &dyn Fn(T, T) -> T, domain_sample: &Vec<T>) -> bool {
-        if domain_sample.len() < 3 {
-            return true;
-        }
-        return permutations(domain_sample, 3).iter().all(|triple| {
+    fn associativity_counterexample(op: &dyn Fn(T, T) -> T, domain_sample: &[T]) -> Option<Vec<T>> {
+        cartesian_power(domain_sample, 3).into_iter().find(|triple| {
             let left_first = (op)((op)(triple[0], triple[1]), triple[2]);
             let right_first = (op)(triple[0], (op)(triple[1], triple[2]));
-            left_first == right_first
-        });
+            left_first != right_first
+        })
     }
 
-    fn identity_holds_over(op: &dyn Fn(T, T) -> T, domain_sample: &[T], identity: T) -> bool {
-        return domain_sample.iter().all(|e| {
-            let from_left = (op)(identity, *e);
-            let from_right = (op)(*e, identity);
-            (*e == from_left) && (*e == from_right)
-        });
+    fn identity_counterexample(
+        op: &dyn Fn(T, T) -> T,
+        domain_sample: &[T],
+        identity: T,
+    ) -> Option<Vec<T>> {
+        domain_sample
+            .iter()
+            .find(|e| {
+                let from_left = (op)(identity, **e);
+                let from_right = (op)(**e, identity);
+                (**e != from_left) || (**e != from_right)
+            })
+            .map(|e| vec![*e])
     }
 
-    fn cancellative_holds_over(op: &dyn Fn(T, T) -> T, domain_sample: &Vec<T>) -> bool {
-        if domain_sample.len() < 3 {
-            return true;
-        }
-        let left_cancellative = permutations(domain_sample, 3).iter().all(|triple| {
-            if (op)(triple[0], triple[1]) == (op)(triple[0], triple[2]) {
-                return triple[1] == triple[2];
-            }
-            true
-        });
-        let right_cancellative = permutations(domain_sample, 3).iter().all(|triple| {
-            if (op)(triple[1], triple[0]) == (op)(triple[2], triple[0]) {
-                return triple[1] == triple[2];
+    fn cancellativity_counterexample(
+        op: &dyn Fn(T, T) -> T,
+        domain_sample: &[T],
+    ) -> Option<Vec<T>> {
+        // Every (a, b, c) combination is checked, since a single cancelling
+        // pair anywhere in the sample is enough to break cancellativity
+        for a in domain_sample {
+            for b in domain_sample {
+                for c in domain_sample {
+                    if b == c {
+                        continue;
+                    }
+                    let left_cancels = (op)(*a, *b) != (op)(*a, *c);
+                    let right_cancels = (op)(*b, *a) != (op)(*c, *a);
+                    if !(left_cancels && right_cancels) {
+                        return Some(vec![*a, *b, *c]);
+                    }
+                }
             }
-            true
-        });
-        left_cancellative && right_cancellative
+        }
+        None
     }
 
-    fn invertibility_holds_over(
+    fn invertibility_counterexample(
         op: &dyn Fn(T, T) -> T,
         inv: &dyn Fn(T, T) -> T,
-        domain_sample: &Vec<T>,
+        domain_sample: &[T],
         identity: T,
-    ) -> bool {
-        if domain_sample.len() < 2 {
-            return true;
-        }
-        return permutations(domain_sample, 2).iter().all(|pair| {
+    ) -> Option<Vec<T>> {
+        cartesian_power(domain_sample, 2).into_iter().find(|pair| {
             let inverse_works = (inv)(pair[0], pair[0]) == identity;
             let left_composition_works = (inv)((op)(pair[0], pair[1]), pair[1]) == pair[0];
-            let right_composition_works = (inv)((op)(pair[1], pair[0]), pair[1]) == pair[0];
-            inverse_works && left_composition_works && right_composition_works
-        });
+            let right_composition_works = (op)((inv)(pair[0], pair[1]), pair[1]) == pair[0];
+            !(inverse_works && left_composition_works && right_composition_works)
+        })
+    }
+
+    fn involutivity_counterexample(
+        op: &dyn Fn(T, T) -> T,
+        involution: &dyn Fn(T) -> T,
+        domain_sample: &[T],
+    ) -> Option<Vec<T>> {
+        let not_self_inverse = domain_sample
+            .iter()
+            .find(|e| (involution)((involution)(**e)) != **e);
+        if let Some(e) = not_self_inverse {
+            return Some(vec![*e]);
+        }
+        cartesian_power(domain_sample, 2).into_iter().find(|pair| {
+            let star_of_product = (involution)((op)(pair[0], pair[1]));
+            let product_of_stars = (op)((involution)(pair[1]), (involution)(pair[0]));
+            star_of_product != product_of_stars
+        })
+    }
+
+    fn idempotency_counterexample(op: &dyn Fn(T, T) -> T, domain_sample: &[T]) -> Option<Vec<T>> {
+        domain_sample
+            .iter()
+            .find(|e| (op)(**e, **e) != **e)
+            .map(|e| vec![*e])
     }
 }
 
@@ -150,6 +358,8 @@ impl<'a, T> PartialEq for PropertyType<'a, T> {
             Self::Cancellative => matches!(other, Self::Cancellative),
             Self::WithIdentity(_) => matches!(other, Self::WithIdentity(_)),
             Self::Invertible(_, _) => matches!(other, Self::Invertible(_, _)),
+            Self::Involutive(_) => matches!(other, Self::Involutive(_)),
+            Self::Idempotent => matches!(other, Self::Idempotent),
         }
     }
 }
@@ -166,7 +376,8 @@ impl<'a, T> PartialEq for PropertyType<'a, T> {
 /// previous inputs every time the operation is called. The existence of the
 /// input history is required by `input_history`, and the caching mechanism is
 /// given by `cache`. The operation itself is given by a reference to a
-/// function via `operation`.
+/// function via `operation`. Operations built `without_history` cache
+/// nothing, so each call only checks properties over its own operands.
 pub trait BinaryOperation<T: Copy + PartialEq> {
     /// Returns a reference to the function underlying the operation
     fn operation(&self) -> &dyn Fn(T, T) -> T;
@@ -183,42 +394,132 @@ pub trait BinaryOperation<T: Copy + PartialEq> {
     fn input_history(&self) -> &Vec<T>;
 
     /// Caches the given `input` to the operation's input history
+    ///
+    /// Inputs already cached are skipped, so the history holds each distinct
+    /// input once, in the order they were first seen.
     fn cache(&mut self, input: T);
 
+    /// Returns how many inputs properties are checked over, operands included
+    ///
+    /// `None`, the default, checks over the entire input history.
+    fn sample_cap(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the inverse of `x` cached by the operation, if it caches inverses
+    fn cached_inverse(&self, _x: T) -> Option<T> {
+        None
+    }
+
+    /// Caches `inverse` as the inverse of `x`, if the operation caches inverses
+    fn cache_inverse(&mut self, _x: T, _inverse: T) {}
+
+    /// Returns the inverse of `x`, if the operation enforces invertibility
+    ///
+    /// Operations caching their inverses, like [`GroupOperation`], answer
+    /// repeated queries from the cache.
+    fn inverse(&mut self, x: T) -> Option<T> {
+        if let Some(inverse) = self.cached_inverse(x) {
+            return Some(inverse);
+        }
+        let inverse = self.properties().into_iter().find_map(|property| match property {
+            PropertyType::Invertible(identity, inv) => Some((inv)(identity, x)),
+            _ => None,
+        })?;
+        self.cache_inverse(x, inverse);
+        Some(inverse)
+    }
+
     /// Returns the result of performing the given operation.
     ///
     /// If the operation is found not to obey all of its stated properties,
-    /// an appropriate Err will be returned; if else, an Ok wrapping the
-    /// proper result of the operation with the given inputs will be returned.
-    fn with(&mut self, left: T, right: T) -> Result<T, PropertyError> {
+    /// an Err carrying the broken law and the inputs witnessing it will be
+    /// returned; if else, an Ok wrapping the proper result of the operation
+    /// with the given inputs will be returned.
+    fn with(&mut self, left: T, right: T) -> Result<T, Counterexample<T>> {
         self.cache(left);
         self.cache(right);
+        let operands = [left, right];
+        let capped;
+        let sample: &[T] = if self.input_history().is_empty() {
+            &operands
+        } else if let Some(cap) = self.sample_cap() {
+            capped = capped_sample(self.input_history(), operands, cap);
+            &capped
+        } else {
+            self.input_history()
+        };
         for property in self.properties() {
-            if property.holds_over(self.operation(), self.input_history()) {
-                continue;
+            if let Some(elements) = property.counterexample(self.operation(), sample) {
+                return Err(Counterexample {
+                    law: property.violation(),
+                    elements,
+                });
             }
-            match property {
-                PropertyType::Commutative | PropertyType::Abelian => {
-                    return Err(PropertyError::CommutativityError);
-                }
-                PropertyType::Associative => {
-                    return Err(PropertyError::AssociativityError);
-                }
-                PropertyType::Cancellative => {
-                    return Err(PropertyError::CancellativityError);
+        }
+        Ok((self.operation())(left, right))
+    }
+}
+
+/// Generates the input-history builders shared by every binary operation wrapper
+macro_rules! history_builders {
+    ($($wrapper:ident<$($param:tt),+> => $input:ty),+ $(,)?) => {
+        $(
+            impl<$($param),+> $wrapper<$($param),+> {
+                /// Returns the given operation without input-history caching
+                ///
+                /// `with` then checks properties over just the operands it's given.
+                pub fn without_history(mut self) -> Self {
+                    self.record_history = false;
+                    self
                 }
-                PropertyType::WithIdentity(_) => {
-                    return Err(PropertyError::IdentityError);
+
+                /// Returns the given operation checking properties over at most `cap` inputs
+                ///
+                /// Only the operands and the latest other inputs, `cap` in
+                /// all, are checked, bounding the cost of each `with` call.
+                /// This trades soundness for speed: violations involving
+                /// older inputs go unnoticed.
+                pub fn with_sample_cap(mut self, cap: usize) -> Self {
+                    self.sample_cap = Some(cap);
+                    self
                 }
-                PropertyType::Invertible(_, _) => {
-                    return Err(PropertyError::InvertibilityError);
+            }
+
+            impl<$($param),+> $wrapper<$($param),+>
+            where
+                $input: Copy + Eq + Hash + 'a,
+            {
+                /// Returns the given operation looking cached inputs up by hash
+                ///
+                /// Caching an input then takes constant time, rather than a
+                /// scan of the whole input history.
+                pub fn with_hashed_history(mut self) -> Self {
+                    let seen: HashSet<$input> = self.history.iter().copied().collect();
+                    self.hashed_history = Some(Box::new(seen));
+                    self
                 }
             }
-        }
-        return Ok((self.operation())(left, right));
-    }
+        )+
+    };
 }
 
+history_builders!(
+    AbelianOperation<'a, T> => T,
+    IdempotentOperation<'a, T> => T,
+    AssociativeOperation<'a, T> => T,
+    CancellativeOperation<'a, T> => T,
+    IdentityOperation<'a, T> => T,
+    MonoidOperation<'a, T> => T,
+    LoopOperation<'a, T> => T,
+    InvertibleOperation<'a, T> => T,
+    GroupOperation<'a, T> => T,
+    InvolutiveOperation<'a, T> => T,
+    OppositeOperation<'a, T> => T,
+    SemidirectProductOperation<'a, N, H> => (N, H),
+    ProductOperation<'a, A, B> => (A, B),
+);
+
 /// A function wrapper enforcing commutativity.
 ///
 /// # Examples
@@ -247,6 +548,9 @@ pub trait BinaryOperation<T: Copy + PartialEq> {
 pub struct AbelianOperation<'a, T> {
     op: &'a dyn Fn(T, T) -> T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
 }
 
 impl<'a, T> AbelianOperation<'a, T> {
@@ -254,6 +558,9 @@ impl<'a, T> AbelianOperation<'a, T> {
         Self {
             op,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
         }
     }
 }
@@ -272,7 +579,79 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for AbelianOperation<'a, T> {
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
+    }
+}
+
+/// A function wrapper enforcing idempotency.
+///
+/// # Examples
+///
+/// ```
+/// # use algae_rs::mapping::IdempotentOperation;
+/// # use algae_rs::mapping::BinaryOperation;
+/// let mut max = IdempotentOperation::new(&|a: i32, b: i32| {
+///     a.max(b)
+/// });
+///
+/// let larger = max.with(1, 2);
+/// assert!(larger.is_ok());
+/// assert!(larger.unwrap() == 2);
+///
+/// let mut add = IdempotentOperation::new(&|a, b| {
+///     a + b
+/// });
+///
+/// let sum = add.with(1, 2);
+/// assert!(sum.is_err());
+/// ```
+pub struct IdempotentOperation<'a, T> {
+    op: &'a dyn Fn(T, T) -> T,
+    history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
+}
+
+impl<'a, T> IdempotentOperation<'a, T> {
+    pub fn new(op: &'a dyn Fn(T, T) -> T) -> Self {
+        Self {
+            op,
+            history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
+        }
+    }
+}
+
+impl<'a, T: Copy + PartialEq> BinaryOperation<T> for IdempotentOperation<'a, T> {
+    fn operation(&self) -> &dyn Fn(T, T) -> T {
+        self.op
+    }
+
+    fn properties(&self) -> Vec<PropertyType<'_, T>> {
+        vec![PropertyType::Idempotent]
+    }
+
+    fn input_history(&self) -> &Vec<T> {
+        &self.history
+    }
+
+    fn cache(&mut self, input: T) {
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
@@ -298,15 +677,18 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for AbelianOperation<'a, T> {
 ///     a / b
 /// });
 ///
+/// // (4 / 4) / 4 != 4 / (4 / 4)
 /// let whole_dividend = div.with(4.0, 2.0);
-/// assert!(whole_dividend.is_ok());
-/// assert!(whole_dividend.unwrap() == 2.0);
+/// assert!(whole_dividend.is_err());
 /// let fractional_dividend = div.with(3.0, 1.0);
 /// assert!(fractional_dividend.is_err());
 /// ```
 pub struct AssociativeOperation<'a, T> {
     op: &'a dyn Fn(T, T) -> T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
 }
 
 impl<'a, T> AssociativeOperation<'a, T> {
@@ -314,6 +696,9 @@ impl<'a, T> AssociativeOperation<'a, T> {
         Self {
             op,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
         }
     }
 }
@@ -332,7 +717,13 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for AssociativeOperation<'a, T>
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
@@ -352,6 +743,9 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for AssociativeOperation<'a, T>
 pub struct CancellativeOperation<'a, T> {
     op: &'a dyn Fn(T, T) -> T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
 }
 
 impl<'a, T> CancellativeOperation<'a, T> {
@@ -359,6 +753,9 @@ impl<'a, T> CancellativeOperation<'a, T> {
         Self {
             op,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
         }
     }
 }
@@ -377,7 +774,13 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for CancellativeOperation<'a, T
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
@@ -407,6 +810,9 @@ pub struct IdentityOperation<'a, T> {
     op: &'a dyn Fn(T, T) -> T,
     identity: T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
 }
 
 impl<'a, T> IdentityOperation<'a, T> {
@@ -415,6 +821,9 @@ impl<'a, T> IdentityOperation<'a, T> {
             op,
             identity,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
         }
     }
 }
@@ -433,7 +842,13 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for IdentityOperation<'a, T> {
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
@@ -459,6 +874,9 @@ pub struct MonoidOperation<'a, T> {
     op: &'a dyn Fn(T, T) -> T,
     identity: T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
 }
 
 impl<'a, T> MonoidOperation<'a, T> {
@@ -467,6 +885,9 @@ impl<'a, T> MonoidOperation<'a, T> {
             op,
             identity,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
         }
     }
 }
@@ -488,7 +909,13 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for MonoidOperation<'a, T> {
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
@@ -514,6 +941,9 @@ pub struct LoopOperation<'a, T> {
     op: &'a dyn Fn(T, T) -> T,
     identity: T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
 }
 
 impl<'a, T> LoopOperation<'a, T> {
@@ -522,6 +952,9 @@ impl<'a, T> LoopOperation<'a, T> {
             op,
             identity,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
         }
     }
 }
@@ -543,7 +976,13 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for LoopOperation<'a, T> {
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
@@ -570,6 +1009,9 @@ pub struct InvertibleOperation<'a, T> {
     inv: &'a dyn Fn(T, T) -> T,
     identity: T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
 }
 
 impl<'a, T> InvertibleOperation<'a, T> {
@@ -579,6 +1021,9 @@ impl<'a, T> InvertibleOperation<'a, T> {
             inv,
             identity,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
         }
     }
 }
@@ -600,7 +1045,13 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for InvertibleOperation<'a, T>
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
@@ -627,6 +1078,33 @@ pub struct GroupOperation<'a, T> {
     inv: &'a dyn Fn(T, T) -> T,
     identity: T,
     history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
+    inverses: Option<Box<dyn InverseCache<T> + 'a>>,
+}
+
+/// Inverses cached by a [`GroupOperation`], erasing the `Eq + Hash` bounds
+trait InverseCache<T> {
+    fn get(&self, x: &T) -> Option<T>;
+
+    fn insert(&mut self, x: T, inverse: T);
+
+    fn len(&self) -> usize;
+}
+
+impl<T: Copy + Eq + Hash> InverseCache<T> for HashMap<T, T> {
+    fn get(&self, x: &T) -> Option<T> {
+        HashMap::get(self, x).copied()
+    }
+
+    fn insert(&mut self, x: T, inverse: T) {
+        HashMap::insert(self, x, inverse);
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
 }
 
 impl<'a, T> GroupOperation<'a, T> {
@@ -636,6 +1114,28 @@ impl<'a, T> GroupOperation<'a, T> {
             inv,
             identity,
             history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
+            inverses: None,
+        }
+    }
+}
+
+impl<'a, T: Copy + Eq + Hash> GroupOperation<'a, T> {
+    /// Returns the given operation caching each inverse once it's found
+    pub fn with_inverse_cache(mut self) -> Self {
+        self.inverses.get_or_insert_with(|| Box::new(HashMap::new()));
+        self
+    }
+
+    /// Caches the inverse of each of `elements` ahead of time
+    ///
+    /// This enables the inverse cache if it isn't already.
+    pub fn cache_inverses(&mut self, elements: &[T]) {
+        self.inverses.get_or_insert_with(|| Box::new(HashMap::new()));
+        for x in elements {
+            self.inverse(*x);
         }
     }
 }
@@ -658,10 +1158,397 @@ impl<'a, T: Copy + PartialEq> BinaryOperation<T> for GroupOperation<'a, T> {
     }
 
     fn cache(&mut self, input: T) {
-        self.history.push(input);
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
+    }
+
+    fn cached_inverse(&self, x: T) -> Option<T> {
+        self.inverses.as_ref()?.get(&x)
+    }
+
+    fn cache_inverse(&mut self, x: T, inverse: T) {
+        if let Some(inverses) = &mut self.inverses {
+            inverses.insert(x, inverse);
+        }
+    }
+}
+
+/// A function wrapper enforcing an anti-involution.
+///
+/// The involution `x ↦ x*` must satisfy `(x*)* == x` and `(a·b)* == b*·a*`.
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::mapping::{InvolutiveOperation, BinaryOperation};
+///
+/// let mut add = InvolutiveOperation::new(&|a, b| a + b, &|a: i32| -a);
+///
+/// let seven = add.with(4, 3);
+/// assert!(seven.is_ok());
+/// assert!(seven.unwrap() == 7);
+///
+/// let mut bad_add = InvolutiveOperation::new(&|a, b| a + b, &|a: i32| a + 1);
+///
+/// let sum = bad_add.with(4, 3);
+/// assert!(sum.is_err());
+/// ```
+pub struct InvolutiveOperation<'a, T> {
+    op: &'a dyn Fn(T, T) -> T,
+    involution: &'a dyn Fn(T) -> T,
+    history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
+}
+
+impl<'a, T> InvolutiveOperation<'a, T> {
+    pub fn new(op: &'a dyn Fn(T, T) -> T, involution: &'a dyn Fn(T) -> T) -> Self {
+        Self {
+            op,
+            involution,
+            history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
+        }
+    }
+}
+
+impl<'a, T: Copy + PartialEq> BinaryOperation<T> for InvolutiveOperation<'a, T> {
+    fn operation(&self) -> &dyn Fn(T, T) -> T {
+        self.op
+    }
+
+    fn properties(&self) -> Vec<PropertyType<'_, T>> {
+        vec![PropertyType::Involutive(self.involution)]
+    }
+
+    fn input_history(&self) -> &Vec<T> {
+        &self.history
+    }
+
+    fn cache(&mut self, input: T) {
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
     }
 }
 
+/// A function wrapper taking the operands of another operation in reverse order.
+///
+/// The opposite operation `a ∘ b = b · a` inherits every property of the
+/// original operation, so the opposite of a group operation is again a group
+/// operation. Its input history is kept separately from the original's.
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::mapping::{AssociativeOperation, OppositeOperation, BinaryOperation};
+///
+/// let first = AssociativeOperation::new(&|a: u32, _: u32| a);
+/// let mut opposite = OppositeOperation::new(&first);
+///
+/// let second = opposite.with(2, 1);
+/// assert!(second.is_ok());
+/// assert!(second.unwrap() == 1);
+/// ```
+pub struct OppositeOperation<'a, T> {
+    original: &'a dyn BinaryOperation<T>,
+    op: Box<dyn Fn(T, T) -> T + 'a>,
+    identity: Option<T>,
+    inv: Option<Box<dyn Fn(T, T) -> T + 'a>>,
+    history: Vec<T>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<T> + 'a>>,
+}
+
+impl<'a, T: Copy + PartialEq + 'a> OppositeOperation<'a, T> {
+    pub fn new(original: &'a dyn BinaryOperation<T>) -> Self {
+        let op = original.operation();
+        let mut opposite = Self {
+            original,
+            op: Box::new(move |a, b| (op)(b, a)),
+            identity: None,
+            inv: None,
+            history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
+        };
+        for property in original.properties() {
+            if let PropertyType::Invertible(identity, inv) = property {
+                // a ∘ b⁻¹ = b⁻¹ · a, where b⁻¹ = inv(identity, b)
+                opposite.identity = Some(identity);
+                opposite.inv = Some(Box::new(move |a, b| (op)((inv)(identity, b), a)));
+            }
+        }
+        opposite
+    }
+}
+
+impl<'a, T: Copy + PartialEq> BinaryOperation<T> for OppositeOperation<'a, T> {
+    fn operation(&self) -> &dyn Fn(T, T) -> T {
+        &self.op
+    }
+
+    fn properties(&self) -> Vec<PropertyType<'_, T>> {
+        self.original
+            .properties()
+            .into_iter()
+            .map(|property| match (property, self.identity, &self.inv) {
+                (PropertyType::Invertible(_, _), Some(identity), Some(inv)) => {
+                    PropertyType::Invertible(identity, inv.as_ref())
+                }
+                (property, _, _) => property,
+            })
+            .collect()
+    }
+
+    fn input_history(&self) -> &Vec<T> {
+        &self.history
+    }
+
+    fn cache(&mut self, input: T) {
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
+    }
+}
+
+type PairFunction<'a, N, H> = Box<dyn Fn((N, H), (N, H)) -> (N, H) + 'a>;
+
+/// A group operation on the semidirect product `N ⋊ H` of two groups.
+///
+/// Given group operations on `N` and `H` and an action `φ` of `H` on `N` by
+/// automorphisms, pairs are multiplied by the twisted rule
+/// `(n₁, h₁)·(n₂, h₂) = (n₁·φ(h₁, n₂), h₁·h₂)`.
+///
+/// # Panics
+///
+/// [`new`](SemidirectProductOperation::new) panics if either `normal` or
+/// `acting` lacks the [`PropertyType::Invertible`] property, since inverses
+/// in the product are built from inverses in each factor.
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::mapping::{BinaryOperation, GroupOperation, SemidirectProductOperation};
+///
+/// let add = GroupOperation::new(&|a: i32, b: i32| a + b, &|a, b| a - b, 0);
+/// let flip = GroupOperation::new(&|a: u8, b: u8| a ^ b, &|a, b| a ^ b, 0);
+/// let reflect = |h: u8, n: i32| if h == 0 { n } else { -n };
+/// let mut infinite_dihedral = SemidirectProductOperation::new(&add, &flip, &reflect);
+///
+/// let product = infinite_dihedral.with((2, 1), (3, 0));
+/// assert!(product.is_ok());
+/// assert!(product.unwrap() == (-1, 1));
+/// ```
+pub struct SemidirectProductOperation<'a, N, H> {
+    op: PairFunction<'a, N, H>,
+    inv: PairFunction<'a, N, H>,
+    identity: (N, H),
+    history: Vec<(N, H)>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<(N, H)> + 'a>>,
+}
+
+impl<'a, N: Copy + PartialEq + 'a, H: Copy + PartialEq + 'a> SemidirectProductOperation<'a, N, H> {
+    pub fn new(
+        normal: &'a dyn BinaryOperation<N>,
+        acting: &'a dyn BinaryOperation<H>,
+        action: &'a dyn Fn(H, N) -> N,
+    ) -> Self {
+        let (n_identity, n_inv) = invertible_parts(normal);
+        let (h_identity, h_inv) = invertible_parts(acting);
+        let (n_op, h_op) = (normal.operation(), acting.operation());
+        let op = move |a: (N, H), b: (N, H)| ((n_op)(a.0, (action)(a.1, b.0)), (h_op)(a.1, b.1));
+        // (n, h)⁻¹ = (φ(h⁻¹, n⁻¹), h⁻¹)
+        let inv = move |a: (N, H), b: (N, H)| {
+            let h_inverse = (h_inv)(h_identity, b.1);
+            let n_inverse = (action)(h_inverse, (n_inv)(n_identity, b.0));
+            op(a, (n_inverse, h_inverse))
+        };
+        Self {
+            op: Box::new(op),
+            inv: Box::new(inv),
+            identity: (n_identity, h_identity),
+            history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
+        }
+    }
+}
+
+impl<'a, N: Copy + PartialEq, H: Copy + PartialEq> BinaryOperation<(N, H)>
+    for SemidirectProductOperation<'a, N, H>
+{
+    fn operation(&self) -> &dyn Fn((N, H), (N, H)) -> (N, H) {
+        &self.op
+    }
+
+    fn properties(&self) -> Vec<PropertyType<'_, (N, H)>> {
+        vec![
+            PropertyType::Associative,
+            PropertyType::WithIdentity(self.identity),
+            PropertyType::Invertible(self.identity, &self.inv),
+        ]
+    }
+
+    fn input_history(&self) -> &Vec<(N, H)> {
+        &self.history
+    }
+
+    fn cache(&mut self, input: (N, H)) {
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
+    }
+}
+
+/// A componentwise operation on the direct product `A × B` of two operations.
+///
+/// Pairs are multiplied by `(a₁, b₁)·(a₂, b₂) = (a₁·a₂, b₁·b₂)`. The product
+/// enforces each property enforced by both of its components.
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::mapping::{BinaryOperation, GroupOperation, ProductOperation};
+///
+/// let add = GroupOperation::new(&|a: i32, b: i32| a + b, &|a, b| a - b, 0);
+/// let mut plane = ProductOperation::new(&add, &add);
+///
+/// let sum = plane.with((1, 2), (3, 4));
+/// assert!(sum.is_ok());
+/// assert!(sum.unwrap() == (4, 6));
+/// ```
+pub struct ProductOperation<'a, A, B> {
+    op: PairFunction<'a, A, B>,
+    commutative: bool,
+    associative: bool,
+    cancellative: bool,
+    identity: Option<(A, B)>,
+    inv: Option<PairFunction<'a, A, B>>,
+    history: Vec<(A, B)>,
+    record_history: bool,
+    sample_cap: Option<usize>,
+    hashed_history: Option<Box<dyn InputSet<(A, B)> + 'a>>,
+}
+
+impl<'a, A: Copy + PartialEq + 'a, B: Copy + PartialEq + 'a> ProductOperation<'a, A, B> {
+    pub fn new(left: &'a dyn BinaryOperation<A>, right: &'a dyn BinaryOperation<B>) -> Self {
+        let (a_op, b_op) = (left.operation(), right.operation());
+        let op = move |x: (A, B), y: (A, B)| ((a_op)(x.0, y.0), (b_op)(x.1, y.1));
+        let inv: Option<PairFunction<'a, A, B>> =
+            if binop_is_invertible(left) && binop_is_invertible(right) {
+                let ((_, a_inv), (_, b_inv)) = (invertible_parts(left), invertible_parts(right));
+                Some(Box::new(move |x: (A, B), y: (A, B)| {
+                    ((a_inv)(x.0, y.0), (b_inv)(x.1, y.1))
+                }))
+            } else {
+                None
+            };
+        let both = |property: PropertyType<'_, A>, same: PropertyType<'_, B>| {
+            left.is(property) && right.is(same)
+        };
+        Self {
+            op: Box::new(op),
+            commutative: both(PropertyType::Commutative, PropertyType::Commutative),
+            associative: both(PropertyType::Associative, PropertyType::Associative),
+            cancellative: both(PropertyType::Cancellative, PropertyType::Cancellative),
+            identity: identity_of(left).zip(identity_of(right)),
+            inv,
+            history: vec![],
+            record_history: true,
+            sample_cap: None,
+            hashed_history: None,
+        }
+    }
+}
+
+impl<'a, A: Copy + PartialEq, B: Copy + PartialEq> BinaryOperation<(A, B)>
+    for ProductOperation<'a, A, B>
+{
+    fn operation(&self) -> &dyn Fn((A, B), (A, B)) -> (A, B) {
+        &self.op
+    }
+
+    fn properties(&self) -> Vec<PropertyType<'_, (A, B)>> {
+        let mut properties = vec![];
+        if self.commutative {
+            properties.push(PropertyType::Commutative);
+        }
+        if self.associative {
+            properties.push(PropertyType::Associative);
+        }
+        if self.cancellative {
+            properties.push(PropertyType::Cancellative);
+        }
+        if let Some(identity) = self.identity {
+            properties.push(PropertyType::WithIdentity(identity));
+            if let Some(inv) = &self.inv {
+                properties.push(PropertyType::Invertible(identity, inv.as_ref()));
+            }
+        }
+        properties
+    }
+
+    fn input_history(&self) -> &Vec<(A, B)> {
+        &self.history
+    }
+
+    fn cache(&mut self, input: (A, B)) {
+        if self.record_history {
+            record(&mut self.history, &mut self.hashed_history, input);
+        }
+    }
+
+    fn sample_cap(&self) -> Option<usize> {
+        self.sample_cap
+    }
+}
+
+fn identity_of<T: Copy + PartialEq>(binop: &dyn BinaryOperation<T>) -> Option<T> {
+    binop.properties().into_iter().find_map(|property| match property {
+        PropertyType::WithIdentity(identity) | PropertyType::Invertible(identity, _) => {
+            Some(identity)
+        }
+        _ => None,
+    })
+}
+
+pub(crate) fn invertible_parts<T: Copy + PartialEq>(
+    binop: &dyn BinaryOperation<T>,
+) -> (T, &dyn Fn(T, T) -> T) {
+    for property in binop.properties() {
+        if let PropertyType::Invertible(identity, inv) = property {
+            return (identity, inv);
+        }
+    }
+    panic!("Operation is not invertible!");
+}
+
 /// Returns whether or not the given [`BinaryOperation`] has the [`PropertyType::Invertible`] property.
 ///
 /// # Examples
@@ -712,17 +1599,176 @@ pub fn binop_has_invertible_identity<T: Copy + PartialEq>(
     false
 }
 
+/// Properties enforceable on unary operations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryProperty {
+    Involutive,
+}
+
+impl UnaryProperty {
+    pub fn holds_over<T: Copy + PartialEq>(&self, f: &dyn Fn(T) -> T, domain_sample: &[T]) -> bool {
+        self.counterexample(f, domain_sample).is_none()
+    }
+
+    /// Returns elements of `domain_sample` witnessing a failure of the property, if any
+    pub fn counterexample<T: Copy + PartialEq>(
+        &self,
+        f: &dyn Fn(T) -> T,
+        domain_sample: &[T],
+    ) -> Option<Vec<T>> {
+        match self {
+            Self::Involutive => domain_sample
+                .iter()
+                .find(|e| (f)((f)(**e)) != **e)
+                .map(|e| vec![*e]),
+        }
+    }
+
+    /// Returns the error reported when the property fails to hold
+    pub fn violation(&self) -> PropertyError {
+        match self {
+            Self::Involutive => PropertyError::InvolutivityError,
+        }
+    }
+}
+
+/// Common interface for unary operations.
+///
+/// This mirrors [`BinaryOperation`]: inputs are cached in an input history,
+/// and the provided `with` checks every property over that history before
+/// returning the result.
+pub trait UnaryOperation<T: Copy + PartialEq> {
+    /// Returns a reference to the function underlying the operation
+    fn operation(&self) -> &dyn Fn(T) -> T;
+
+    /// Vec of all enforced properties
+    fn properties(&self) -> Vec<UnaryProperty>;
+
+    /// Returns a reference to a Vec of all previous inputs to the operation
+    fn input_history(&self) -> &Vec<T>;
+
+    /// Caches the given `input` to the operation's input history
+    fn cache(&mut self, input: T);
+
+    /// Returns the result of performing the given operation.
+    ///
+    /// If the operation is found not to obey all of its stated properties,
+    /// an Err carrying the broken law and the input witnessing it will be
+    /// returned; if else, an Ok wrapping the proper result of the operation
+    /// with the given input will be returned.
+    fn with(&mut self, x: T) -> Result<T, Counterexample<T>> {
+        self.cache(x);
+        let operand = [x];
+        let sample: &[T] = if self.input_history().is_empty() {
+            &operand
+        } else {
+            self.input_history()
+        };
+        for property in self.properties() {
+            if let Some(elements) = property.counterexample(self.operation(), sample) {
+                return Err(Counterexample {
+                    law: property.violation(),
+                    elements,
+                });
+            }
+        }
+        Ok((self.operation())(x))
+    }
+}
+
+/// A unary function wrapper enforcing `f(f(x)) == x`.
+///
+/// # Examples
+///
+/// ```
+/// # use algae_rs::mapping::Involution;
+/// # use algae_rs::mapping::UnaryOperation;
+/// let mut negate = Involution::new(&|x: i32| -x);
+///
+/// let negation = negate.with(3);
+/// assert!(negation.is_ok());
+/// assert!(negation.unwrap() == -3);
+///
+/// let mut increment = Involution::new(&|x: i32| x + 1);
+///
+/// let successor = increment.with(3);
+/// assert!(successor.is_err());
+/// ```
+pub struct Involution<'a, T> {
+    op: &'a dyn Fn(T) -> T,
+    history: Vec<T>,
+    record_history: bool,
+}
+
+impl<'a, T> Involution<'a, T> {
+    pub fn new(op: &'a dyn Fn(T) -> T) -> Self {
+        Self {
+            op,
+            history: vec![],
+            record_history: true,
+        }
+    }
+
+    /// Returns the given operation without input-history caching
+    ///
+    /// `with` then checks properties over just the operand it's given.
+    pub fn without_history(mut self) -> Self {
+        self.record_history = false;
+        self
+    }
+}
+
+impl<'a, T: Copy + PartialEq> UnaryOperation<T> for Involution<'a, T> {
+    fn operation(&self) -> &dyn Fn(T) -> T {
+        self.op
+    }
+
+    fn properties(&self) -> Vec<UnaryProperty> {
+        vec![UnaryProperty::Involutive]
+    }
+
+    fn input_history(&self) -> &Vec<T> {
+        &self.history
+    }
+
+    fn cache(&mut self, input: T) {
+        if self.record_history && !self.history.contains(&input) {
+            self.history.push(input);
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 #[cfg(test)]
 mod tests {
 
-    use super::{cayley_product, permutations};
+    use std::cell::Cell;
+
+    use super::{
+        cayley_product, is_distributive_over, permutations, AbelianOperation, AssociativeOperation,
+        BinaryOperation, GroupOperation, Involution, ProductOperation, PropertyError, PropertyType,
+        UnaryOperation,
+    };
 
     #[test]
     fn pair_permutations() {
         let v = &[1, 2, 3];
         let pairs = permutations(v, 2);
-        assert!(pairs.contains(&vec![1, 2]));
-        assert!(pairs.contains(&vec![3, 2]));
+        assert!(
+            pairs
+                == vec![
+                    vec![1, 2],
+                    vec![1, 3],
+                    vec![2, 1],
+                    vec![2, 3],
+                    vec![3, 1],
+                    vec![3, 2]
+                ]
+        );
+        assert!(permutations(v, 3).len() == 6);
+        assert!(permutations(v, 4).is_empty());
     }
 
     #[test]
@@ -744,4 +1790,225 @@ mod tests {
                 ]
         );
     }
+
+    #[test]
+    fn cayley_product_of_words() {
+        let words = vec![String::from("ab"), String::from("ba")];
+        let product = cayley_product(&words);
+        assert!(product.len() == 4);
+        for x in &words {
+            for y in &words {
+                assert!(product.contains(&vec![x.clone(), y.clone()]));
+            }
+        }
+    }
+
+    #[test]
+    fn subtraction_counterexample() {
+        let mut sub = AssociativeOperation::new(&|a: i32, b: i32| a - b);
+        let failure = sub.with(3, 4).unwrap_err();
+        assert!(matches!(failure.law, PropertyError::AssociativityError));
+        assert!(failure.elements == vec![3, 3, 3]);
+        let [a, b, c] = failure.elements[..] else {
+            panic!()
+        };
+        assert!((a - b) - c != a - (b - c));
+    }
+
+    #[test]
+    fn repeated_element_violations() {
+        // Associative on distinct triples, where it agrees with `max`, but
+        // not once an element is repeated
+        let op = |a: i32, b: i32| if a == b { a + 100 } else { a.max(b) };
+        let distinct = [1, 2, 3];
+        assert!(super::permutations(&distinct, 3).iter().all(|t| {
+            (op)((op)(t[0], t[1]), t[2]) == (op)(t[0], (op)(t[1], t[2]))
+        }));
+        let mut broken = AssociativeOperation::new(&op);
+        let failure = broken.with(1, 2).unwrap_err();
+        assert!(matches!(failure.law, PropertyError::AssociativityError));
+        assert!(failure.elements == vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn capped_samples() {
+        let evaluations = Cell::new(0);
+        let add = |a: u32, b: u32| {
+            evaluations.set(evaluations.get() + 1);
+            a + b
+        };
+        let mut capped_add = AssociativeOperation::new(&add).with_sample_cap(3);
+        for x in 0..1_000 {
+            evaluations.set(0);
+            assert!(capped_add.with(x, x + 1).unwrap() == 2 * x + 1);
+            assert!(evaluations.get() <= 27 * 4 + 1);
+        }
+        assert!(capped_add.input_history().len() == 1_001);
+
+        let mut sub = AssociativeOperation::new(&|a: i32, b: i32| a - b).with_sample_cap(3);
+        assert!(sub.with(1, 2).is_err());
+        let failure = sub.with(3, 4).unwrap_err();
+        assert!(matches!(failure.law, PropertyError::AssociativityError));
+        assert!(failure.elements.iter().all(|e| [2, 3, 4].contains(e)));
+    }
+
+    #[test]
+    fn unary_counterexample() {
+        let mut negate = Involution::new(&|x: i32| -x);
+        assert!(negate.with(3).unwrap() == -3);
+        let mut increment = Involution::new(&|x: i32| x + 1);
+        let failure = increment.with(3).unwrap_err();
+        assert!(matches!(failure.law, PropertyError::InvolutivityError));
+        assert!(failure.elements == vec![3]);
+    }
+
+    #[test]
+    fn sample_cap_keeps_reused_operands() {
+        let add = |a: i32, b: i32| if (a, b) == (1, 2) { 0 } else { a + b };
+        let mut capped_add = AbelianOperation::new(&add).with_sample_cap(3);
+        assert!(capped_add.with(1, 3).is_ok());
+        assert!(capped_add.with(2, 4).is_ok());
+        assert!(capped_add.with(5, 6).is_ok());
+        let failure = capped_add.with(1, 2).unwrap_err();
+        assert!(matches!(failure.law, PropertyError::CommutativityError));
+        assert!(failure.elements.contains(&1) && failure.elements.contains(&2));
+        assert!(capped_add.input_history() == &vec![1, 3, 2, 4, 5, 6]);
+    }
+
+    #[test]
+    fn deduplicated_history() {
+        let mut add = AbelianOperation::new(&|a: i32, b: i32| a + b);
+        for _ in 0..1_000 {
+            assert!(add.with(1, 2).unwrap() == 3);
+        }
+        assert!(add.input_history() == &vec![1, 2]);
+
+        let mut sub = AbelianOperation::new(&|a: i32, b: i32| a - b);
+        assert!(sub.with(1, 1).is_ok());
+        assert!(sub.input_history().len() == 1);
+        assert!(sub.with(1, 2).is_err());
+    }
+
+    #[test]
+    fn deduplicated_repeated_element_violations() {
+        // Agrees with `max` except that 2·2 = 102, so associativity breaks
+        // only on triples repeating 2 alongside a larger element
+        let op = |a: i32, b: i32| if (a, b) == (2, 2) { 102 } else { a.max(b) };
+        let mut broken = AssociativeOperation::new(&op);
+        let mut hashed = AssociativeOperation::new(&op).with_hashed_history();
+        for broken in [&mut broken, &mut hashed] {
+            assert!(broken.with(1, 2).is_ok());
+            assert!(broken.with(1, 2).is_ok());
+            assert!(broken.input_history() == &vec![1, 2]);
+            let failure = broken.with(3, 1).unwrap_err();
+            assert!(matches!(failure.law, PropertyError::AssociativityError));
+            assert!(failure.elements.iter().filter(|e| **e == 2).count() == 2);
+            assert!(broken.input_history() == &vec![1, 2, 3]);
+        }
+    }
+
+    #[test]
+    fn distributivity() {
+        let add = |a: i32, b: i32| a + b;
+        let mul = |a: i32, b: i32| a * b;
+        let max = |a: i32, b: i32| a.max(b);
+        let sample: Vec<i32> = (-3..4).collect();
+        assert!(is_distributive_over(&mul, &add, &sample));
+        assert!(!is_distributive_over(&max, &add, &sample));
+    }
+
+    #[test]
+    fn cached_inverses() {
+        let inversions = Cell::new(0);
+        let sub = |a: u8, b: u8| {
+            inversions.set(inversions.get() + 1);
+            (a + 7 - b) % 7
+        };
+        let mut z7 = GroupOperation::new(&|a: u8, b: u8| (a + b) % 7, &sub, 0);
+        z7.cache_inverses(&[1, 2, 3]);
+        assert!(inversions.get() == 3);
+        for x in 0..7 {
+            assert!(z7.inverse(x) == Some(sub(0, x)));
+        }
+        assert!(z7.inverses.as_ref().is_some_and(|inverses| inverses.len() == 7));
+        assert!(inversions.get() == 3 + 4 + 7);
+
+        let mut uncached = GroupOperation::new(&|a: u8, b: u8| (a + b) % 7, &sub, 0);
+        inversions.set(0);
+        assert!(uncached.inverse(3) == Some(4) && uncached.inverse(3) == Some(4));
+        assert!(inversions.get() == 2);
+    }
+
+    #[test]
+    fn product_of_additions() {
+        let add = GroupOperation::new(&|a: i32, b: i32| a + b, &|a, b| a - b, 0);
+        let shift = AbelianOperation::new(&|a: i32, b: i32| a + b);
+        let mut pairs = ProductOperation::new(&add, &add);
+        assert!(pairs.with((1, 2), (3, -4)).unwrap() == (4, -2));
+        assert!(pairs.is(PropertyType::Associative));
+        assert!(pairs.is(PropertyType::WithIdentity((0, 0))));
+        assert!(!pairs.is(PropertyType::Commutative));
+
+        let mixed = ProductOperation::new(&add, &shift);
+        assert!(mixed.properties().is_empty());
+    }
+
+    #[test]
+    fn custom_error_messages() {
+        let error = PropertyError::from("Operation is not idempotent!");
+        assert!(matches!(&error, PropertyError::Other(_)));
+        assert!(error.message() == "Operation is not idempotent!");
+        assert!(error.to_string() == "Operation is not idempotent!");
+        let error: PropertyError = format!("{} is not a unit", 4).into();
+        assert!(error.message() == "4 is not a unit");
+        assert!(PropertyError::ClosureError.message() == "Operation is not closed over its set!");
+    }
+
+    #[test]
+    fn transpose_is_involutive() {
+        type Matrix = [[i32; 2]; 2];
+        let matmul = |a: Matrix, b: Matrix| {
+            let mut product = [[0; 2]; 2];
+            for (i, row) in product.iter_mut().enumerate() {
+                for (j, entry) in row.iter_mut().enumerate() {
+                    *entry = a[i][0] * b[0][j] + a[i][1] * b[1][j];
+                }
+            }
+            product
+        };
+        let transpose = |a: Matrix| [[a[0][0], a[1][0]], [a[0][1], a[1][1]]];
+        let negate = |a: Matrix| [[-a[0][0], -a[0][1]], [-a[1][0], -a[1][1]]];
+        let sample = vec![[[1, 2], [3, 4]], [[0, 1], [1, 0]], [[2, 0], [5, -1]]];
+        assert!(PropertyType::Involutive(&transpose).holds_over(&matmul, &sample));
+        assert!(!PropertyType::Involutive(&negate).holds_over(&matmul, &sample));
+    }
+
+    #[test]
+    fn conjugation_is_involutive() {
+        let mul = |a: (i32, i32), b: (i32, i32)| (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0);
+        let conjugate = |a: (i32, i32)| (a.0, -a.1);
+        let swap = |a: (i32, i32)| (a.1, a.0);
+        let sample = vec![(1, 2), (3, -1), (0, 4)];
+        assert!(PropertyType::Involutive(&conjugate).holds_over(&mul, &sample));
+        assert!(!PropertyType::Involutive(&swap).holds_over(&mul, &sample));
+    }
+
+    #[test]
+    fn cancellativity_checks_every_triple() {
+        let mul = |a: i32, b: i32| a * b;
+        assert!(!PropertyType::Cancellative.holds_over(&mul, &[1, 0, 2]));
+        assert!(PropertyType::Cancellative.holds_over(&mul, &[1, 3, 2]));
+        let add = |a: i32, b: i32| a + b;
+        assert!(PropertyType::Cancellative.holds_over(&add, &[1, 0, 2]));
+    }
+
+    #[test]
+    fn holds_over_slices() {
+        let add = |a: i32, b: i32| (a + b) % 4;
+        let sample = [0, 1, 2, 3, 4, 5];
+        assert!(PropertyType::Commutative.holds_over(&add, &sample[..4]));
+        assert!(PropertyType::Associative.holds_over(&add, &sample[..4]));
+        assert!(PropertyType::WithIdentity(0).holds_over(&add, &sample[..4]));
+        assert!(!PropertyType::WithIdentity(0).holds_over(&add, &sample));
+    }
 }
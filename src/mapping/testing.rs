@@ -0,0 +1,199 @@
+//! Randomized checking of binary operations against their laws.
+//!
+//! Unlike the history-based checks on [`BinaryOperation`](super::BinaryOperation),
+//! [`verify_property`] draws fresh inputs, so violations turn up before the
+//! offending operands are ever passed to `with`.
+
+use super::{Counterexample, PropertyError};
+
+/// The number of input tuples [`verify_property`] draws before giving up
+const CASES: usize = 256;
+
+/// The laws [`verify_property`] can check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropertyKind {
+    Commutative,
+    Associative,
+    Cancellative,
+    Idempotent,
+}
+
+impl PropertyKind {
+    fn arity(self) -> usize {
+        match self {
+            PropertyKind::Idempotent => 1,
+            PropertyKind::Commutative => 2,
+            PropertyKind::Associative | PropertyKind::Cancellative => 3,
+        }
+    }
+
+    fn law(self) -> PropertyError {
+        match self {
+            PropertyKind::Commutative => PropertyError::CommutativityError,
+            PropertyKind::Associative => PropertyError::AssociativityError,
+            PropertyKind::Cancellative => PropertyError::CancellativityError,
+            PropertyKind::Idempotent => PropertyError::IdempotencyError,
+        }
+    }
+
+    fn is_violated_by<T: Copy + PartialEq>(self, op: &dyn Fn(T, T) -> T, elements: &[T]) -> bool {
+        match *elements {
+            [a] if self == PropertyKind::Idempotent => (op)(a, a) != a,
+            [a, b] if self == PropertyKind::Commutative => (op)(a, b) != (op)(b, a),
+            [a, b, c] if self == PropertyKind::Associative => {
+                (op)((op)(a, b), c) != (op)(a, (op)(b, c))
+            }
+            [a, b, c] if self == PropertyKind::Cancellative => {
+                b != c && ((op)(a, b) == (op)(a, c) || (op)(b, a) == (op)(c, a))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A linear congruential generator, seeded deterministically so that
+/// failures reproduce from run to run
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 32 pseudorandom bits
+    pub fn next_u32(&mut self) -> u32 {
+        self.state = self
+            .state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.state >> 32) as u32
+    }
+}
+
+/// Types [`verify_property`] can generate and shrink.
+pub trait Arbitrary: Sized {
+    /// Draws a value from `rng`
+    fn arbitrary(rng: &mut Rng) -> Self;
+
+    /// Returns values simpler than `self`, simplest first
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! arbitrary_integers {
+    ($($int:ty),+) => {
+        $(
+            impl Arbitrary for $int {
+                fn arbitrary(rng: &mut Rng) -> Self {
+                    let bits = (u64::from(rng.next_u32()) << 32) | u64::from(rng.next_u32());
+                    // Small values break laws far more often than large ones
+                    if bits & 3 == 0 {
+                        bits as $int
+                    } else {
+                        (bits % 33) as $int
+                    }
+                }
+
+                #[allow(unused_comparisons)]
+                fn shrink(&self) -> Vec<Self> {
+                    let x = *self;
+                    let mut simpler = vec![];
+                    let step = if x < 0 { x + 1 } else if x > 0 { x - 1 } else { x };
+                    for candidate in [0, x / 2, step] {
+                        if candidate != x && !simpler.contains(&candidate) {
+                            simpler.push(candidate);
+                        }
+                    }
+                    simpler
+                }
+            }
+        )+
+    };
+}
+
+arbitrary_integers!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Checks that `op` satisfies `property` over randomly drawn inputs
+///
+/// On failure, the offending elements are shrunk one at a time for as long
+/// as the law stays broken, so the reported counterexample is minimal in
+/// the sense of [`Arbitrary::shrink`].
+///
+/// # Examples
+///
+/// ```
+/// use algae_rs::mapping::testing::{verify_property, PropertyKind};
+///
+/// assert!(verify_property(&|a: i32, b| a.wrapping_add(b), PropertyKind::Commutative).is_ok());
+///
+/// let counterexample =
+///     verify_property(&|a: i32, b| a.wrapping_sub(b), PropertyKind::Commutative).unwrap_err();
+/// assert!(counterexample.elements == vec![0, 1]);
+/// ```
+pub fn verify_property<T: Arbitrary + Copy + PartialEq>(
+    op: &dyn Fn(T, T) -> T,
+    property: PropertyKind,
+) -> Result<(), Counterexample<T>> {
+    let mut rng = Rng::new(0x5eed);
+    for _ in 0..CASES {
+        let elements: Vec<T> = (0..property.arity()).map(|_| T::arbitrary(&mut rng)).collect();
+        if property.is_violated_by(op, &elements) {
+            return Err(Counterexample {
+                law: property.law(),
+                elements: shrink(op, property, elements),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn shrink<T: Arbitrary + Copy + PartialEq>(
+    op: &dyn Fn(T, T) -> T,
+    property: PropertyKind,
+    mut elements: Vec<T>,
+) -> Vec<T> {
+    let mut shrunk = true;
+    while shrunk {
+        shrunk = false;
+        for i in 0..elements.len() {
+            for candidate in elements[i].shrink() {
+                let mut attempt = elements.clone();
+                attempt[i] = candidate;
+                if property.is_violated_by(op, &attempt) {
+                    elements = attempt;
+                    shrunk = true;
+                    break;
+                }
+            }
+        }
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn catches_non_commutative_subtraction() {
+        let sub = |a: i32, b: i32| a.wrapping_sub(b);
+        let counterexample = verify_property(&sub, PropertyKind::Commutative).unwrap_err();
+        assert!(matches!(counterexample.law, PropertyError::CommutativityError));
+        let [a, b] = counterexample.elements[..] else {
+            panic!()
+        };
+        assert!(sub(a, b) != sub(b, a));
+        assert!(counterexample.elements == vec![0, 1]);
+        assert!(verify_property(&sub, PropertyKind::Associative).is_err());
+    }
+
+    #[test]
+    fn lawful_operations_pass() {
+        assert!(verify_property(&|a: u8, b| a ^ b, PropertyKind::Commutative).is_ok());
+        assert!(verify_property(&|a: u8, b| a ^ b, PropertyKind::Associative).is_ok());
+        assert!(verify_property(&|a: u8, b| a ^ b, PropertyKind::Cancellative).is_ok());
+        assert!(verify_property(&|a: u8, b| a.max(b), PropertyKind::Idempotent).is_ok());
+    }
+}